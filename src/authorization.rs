@@ -0,0 +1,240 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use std::{collections::HashSet, hash::BuildHasher};
+
+use serde::Serialize;
+
+use crate::{Action, Document, EmbeddedRepresentation, Entity, Link};
+
+/// Policy that decides which permissions the caller of an API holds, used by
+/// [`Document::authorize`] to remove the parts of a document that the caller may not use.
+pub trait Policy {
+    /// Check if the caller holds the given permission.
+    ///
+    /// # Parameters
+    /// - `permission` - The permission to check.
+    fn allows(&self, permission: &str) -> bool;
+}
+
+impl<F> Policy for F
+where
+    F: Fn(&str) -> bool,
+{
+    fn allows(&self, permission: &str) -> bool {
+        self(permission)
+    }
+}
+
+impl<H> Policy for HashSet<String, H>
+where
+    H: BuildHasher,
+{
+    fn allows(&self, permission: &str) -> bool {
+        self.contains(permission)
+    }
+}
+
+/// Policy that grants no permissions at all.
+#[cfg(feature = "axum")]
+pub(crate) struct DenyAll;
+
+#[cfg(feature = "axum")]
+impl Policy for DenyAll {
+    fn allows(&self, _: &str) -> bool {
+        false
+    }
+}
+
+/// Check if the given policy allows every one of the required permissions.
+fn permitted(policy: &dyn Policy, requires: &[String]) -> bool {
+    requires.iter().all(|permission| policy.allows(permission))
+}
+
+impl<T> Document<T>
+where
+    T: Serialize,
+{
+    /// Remove every action, link and embedded entity from the document that requires a
+    /// permission the given policy does not allow, including those inside embedded
+    /// representations.
+    ///
+    /// When rendered as an Axum response, anything that requires a permission is removed unless
+    /// the handler is wrapped by the `AuthorizationLayer` from the `axum` module, which provides
+    /// the policy of the caller.
+    ///
+    /// # Parameters
+    /// - `policy` - The policy to authorize the caller with.
+    pub fn authorize(mut self, policy: &dyn Policy) -> Self {
+        self.entities = authorize_entities(self.entities, policy);
+        self.links.retain(|link| permitted(policy, &link.requires));
+        self.actions
+            .retain(|action| permitted(policy, &action.requires));
+
+        self
+    }
+
+    /// Check if anything in the document requires a permission.
+    #[must_use]
+    pub fn is_restricted(&self) -> bool {
+        entities_restricted(&self.entities)
+            || self.links.iter().any(|link| !link.requires.is_empty())
+            || self.actions.iter().any(|action| !action.requires.is_empty())
+    }
+
+}
+
+/// Remove the embedded entities that the policy does not allow, and apply the policy to the
+/// contents of the remaining embedded representations.
+fn authorize_entities(entities: Vec<Entity>, policy: &dyn Policy) -> Vec<Entity> {
+    entities
+        .into_iter()
+        .filter_map(|entity| match entity {
+            Entity::Link(link) if permitted(policy, &link.requires) => Some(Entity::Link(link)),
+            Entity::Representation(mut representation)
+                if permitted(policy, &representation.requires) =>
+            {
+                representation.entities = authorize_entities(representation.entities, policy);
+                representation
+                    .links
+                    .retain(|link| permitted(policy, &link.requires));
+                representation
+                    .actions
+                    .retain(|action| permitted(policy, &action.requires));
+
+                Some(Entity::Representation(representation))
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Check if any of the given embedded entities, or anything within them, requires a permission.
+fn entities_restricted(entities: &[Entity]) -> bool {
+    entities.iter().any(|entity| match entity {
+        Entity::Link(link) => !link.requires.is_empty(),
+        Entity::Representation(representation) => {
+            !representation.requires.is_empty()
+                || entities_restricted(&representation.entities)
+                || representation.links.iter().any(|l| !l.requires.is_empty())
+                || representation.actions.iter().any(|a| !a.requires.is_empty())
+        },
+    })
+}
+
+impl Action {
+    /// Specify a permission that the caller must hold to be able to use this action.
+    ///
+    /// # Parameters
+    /// - `permission` - The required permission.
+    pub fn requires<S>(mut self, permission: S) -> Self
+    where
+        S: ToString,
+    {
+        self.requires.push(permission.to_string());
+
+        self
+    }
+}
+
+impl Link {
+    /// Specify a permission that the caller must hold to be able to see this link.
+    ///
+    /// # Parameters
+    /// - `permission` - The required permission.
+    pub fn requires<S>(mut self, permission: S) -> Self
+    where
+        S: ToString,
+    {
+        self.requires.push(permission.to_string());
+
+        self
+    }
+}
+
+impl EmbeddedRepresentation {
+    /// Specify a permission that the caller must hold to be able to see this representation.
+    ///
+    /// # Parameters
+    /// - `permission` - The required permission.
+    pub fn requires<S>(mut self, permission: S) -> Self
+    where
+        S: ToString,
+    {
+        self.requires.push(permission.to_string());
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn document() -> Document<Value> {
+        Document::new(json!({"orderNumber": 42}))
+            .with_embedded_representation(
+                EmbeddedRepresentation::new(json!({"customerId": "pj123"}))
+                    .with_rel("customer")
+                    .with_action(
+                        Action::new("edit", "/customers/pj123").requires("customers:write"),
+                    )
+                    .with_embedded_link(
+                        Link::new("/customers/pj123/notes").requires("notes:read"),
+                    ),
+            )
+            .with_embedded_representation(
+                EmbeddedRepresentation::new(json!({})).with_rel("audit").requires("audit:read"),
+            )
+            .with_link(Link::new("/orders/42").with_rel("self"))
+            .with_link(
+                Link::new("/orders/42/invoice")
+                    .with_rel("invoice")
+                    .requires("billing:read"),
+            )
+            .with_action(Action::new("cancel", "/orders/42").requires("orders:write"))
+            .with_action(Action::new("add-item", "/orders/42/items"))
+    }
+
+    #[test]
+    fn authorize_everything() {
+        let document = document().authorize(&|_: &str| true);
+
+        check!(document.entities.len() == 2);
+        check!(document.links.len() == 2);
+        check!(document.actions.len() == 2);
+    }
+
+    #[test]
+    fn authorize_some() {
+        let permissions: HashSet<String> =
+            ["orders:write".to_owned(), "notes:read".to_owned()].into();
+        let document = document().authorize(&permissions);
+
+        check!(
+            serde_json::to_value(&document).unwrap()
+                == json!({
+                    "properties": {"orderNumber": 42},
+                    "entities": [{
+                        "rel": ["customer"],
+                        "properties": {"customerId": "pj123"},
+                        "entities": [{"href": "/customers/pj123/notes"}]
+                    }],
+                    "links": [{"rel": ["self"], "href": "/orders/42"}],
+                    "actions": [
+                        {"name": "cancel", "href": "/orders/42"},
+                        {"name": "add-item", "href": "/orders/42/items"}
+                    ]
+                })
+        );
+    }
+
+    #[test]
+    fn restricted() {
+        check!(document().is_restricted());
+        check!(!document().authorize(&|_: &str| false).is_restricted());
+        check!(!Document::new(json!({})).is_restricted());
+    }
+}
@@ -0,0 +1,28 @@
+//! Additional support for the [Axum](https://crates.io/crates/axum) HTTP Server, beyond returning
+//! [`Response`](crate::Response) values from handlers.
+
+mod authorization;
+mod body;
+mod conditional;
+mod errors;
+mod methods;
+mod mock;
+mod prefer;
+mod profile;
+mod search;
+mod sparse;
+mod transform;
+mod wrap;
+
+pub use authorization::*;
+pub(crate) use body::*;
+pub use conditional::*;
+pub use errors::*;
+pub use methods::*;
+pub use mock::*;
+pub use prefer::*;
+pub use profile::*;
+pub use search::*;
+pub use sparse::*;
+pub use transform::*;
+pub use wrap::*;
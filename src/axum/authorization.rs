@@ -0,0 +1,281 @@
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::response::Response;
+use http::Request;
+use tower::{Layer, Service};
+
+use crate::Policy;
+
+tokio::task_local! {
+    /// The policy of the caller whose request is being handled, set by [`AuthorizationLayer`] for
+    /// as long as the wrapped service is producing its response.
+    static POLICY: Arc<dyn Policy + Send + Sync>;
+}
+
+/// Get the policy of the caller whose request is being handled, if an [`AuthorizationLayer`] has
+/// provided one.
+pub(crate) fn current_policy() -> Option<Arc<dyn Policy + Send + Sync>> {
+    POLICY.try_with(Clone::clone).ok()
+}
+
+/// Layer that renders Siren responses with the actions, links and embedded entities that the
+/// caller is permitted to use, as described by [`Document::authorize`](crate::Document::authorize).
+///
+/// The caller is taken from the extensions of the request, as a value of type `P` which is
+/// normally inserted by an authentication middleware that runs before this layer. If the request
+/// has no such extension then everything that requires a permission is removed.
+///
+/// The policy is applied when the handler renders its [`Response`](crate::Response), so any
+/// layers between this one and the handler, such as
+/// [`SparseFieldsetLayer`](super::SparseFieldsetLayer) or
+/// [`ConditionalRequestLayer`](super::ConditionalRequestLayer), only ever see the document that
+/// the caller is permitted to see. This layer should wrap any layers that make extra `GET`
+/// requests to the wrapped service, so that those requests are authorized in the same way.
+///
+/// ```rust,no_run
+/// # use axum::{routing::get, Router};
+/// # use http_siren::{axum::AuthorizationLayer, Policy};
+/// # async fn get_order() -> http_siren::Response<()> { todo!() }
+/// #[derive(Clone)]
+/// struct Principal {
+///     permissions: Vec<String>,
+/// }
+///
+/// impl Policy for Principal {
+///     fn allows(&self, permission: &str) -> bool {
+///         self.permissions.iter().any(|p| p == permission)
+///     }
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/orders/:id", get(get_order))
+///     .layer(AuthorizationLayer::<Principal>::new());
+/// ```
+pub struct AuthorizationLayer<P> {
+    _policy: PhantomData<fn() -> P>,
+}
+
+impl<P> AuthorizationLayer<P> {
+    /// Create a new layer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _policy: PhantomData,
+        }
+    }
+}
+
+impl<P> Default for AuthorizationLayer<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Clone for AuthorizationLayer<P> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Debug for AuthorizationLayer<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthorizationLayer").finish()
+    }
+}
+
+impl<S, P> Layer<S> for AuthorizationLayer<P> {
+    type Service = Authorization<S, P>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Authorization {
+            inner,
+            _policy: PhantomData,
+        }
+    }
+}
+
+/// Service produced by [`AuthorizationLayer`].
+pub struct Authorization<S, P> {
+    inner:   S,
+    _policy: PhantomData<fn() -> P>,
+}
+
+impl<S, P> Clone for Authorization<S, P>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner:   self.inner.clone(),
+            _policy: PhantomData,
+        }
+    }
+}
+
+impl<S, P> Debug for Authorization<S, P>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Authorization")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<S, P, B> Service<Request<B>> for Authorization<S, P>
+where
+    S: Service<Request<B>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+    P: Policy + Clone + Send + Sync + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let policy = match request.extensions().get::<P>().cloned() {
+            Some(policy) => policy,
+            None => return Box::pin(self.inner.call(request)),
+        };
+        let policy: Arc<dyn Policy + Send + Sync> = Arc::new(policy);
+
+        // Services may render their response either when called or when their future is polled,
+        // so the policy needs to be available for both.
+        let future = POLICY.sync_scope(policy.clone(), || self.inner.call(request));
+
+        Box::pin(POLICY.scope(policy, future))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use assert2::check;
+    use axum::{body::Body, routing::get, Extension, Router};
+    use http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderValue, StatusCode,
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        axum::{ConditionalRequestLayer, SparseFieldsetLayer},
+        Action, Document,
+    };
+
+    async fn get_order() -> crate::Response<Value> {
+        Document::new(json!({"orderNumber": 42, "status": "pending"}))
+            .with_action(Action::new("cancel", "/orders/42").requires("orders:write"))
+            .with_action(Action::new("add-item", "/orders/42/items"))
+            .into()
+    }
+
+    async fn send(permissions: Option<&[&str]>) -> Value {
+        let router = Router::new()
+            .route("/orders/42", get(get_order))
+            .layer(AuthorizationLayer::<HashSet<String>>::new());
+
+        let response = match permissions {
+            Some(permissions) => {
+                let permissions: HashSet<String> =
+                    permissions.iter().map(ToString::to_string).collect();
+                router
+                    .layer(Extension(permissions))
+                    .oneshot(Request::get("/orders/42").body(Body::empty()).unwrap())
+                    .await
+            },
+            None => {
+                router
+                    .oneshot(Request::get("/orders/42").body(Body::empty()).unwrap())
+                    .await
+            },
+        }
+        .unwrap();
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn permitted() {
+        let body = send(Some(&["orders:write"])).await;
+
+        check!(body["actions"].as_array().unwrap().len() == 2);
+    }
+
+    #[tokio::test]
+    async fn not_permitted() {
+        let body = send(Some(&["orders:read"])).await;
+
+        check!(body["actions"] == json!([{"name": "add-item", "href": "/orders/42/items"}]));
+    }
+
+    #[tokio::test]
+    async fn no_principal() {
+        let body = send(None).await;
+
+        check!(body["actions"] == json!([{"name": "add-item", "href": "/orders/42/items"}]));
+    }
+
+    #[tokio::test]
+    async fn stacked_with_sparse_fieldsets_and_etags() {
+        async fn get_tagged_order() -> crate::Response<Value> {
+            crate::Response::from(get_order().await.document).with_etag()
+        }
+
+        let router: Router = Router::new()
+            .route("/orders/42", get(get_tagged_order))
+            .layer(SparseFieldsetLayer::new());
+        let router = ConditionalRequestLayer::new().layer(router);
+        let app = AuthorizationLayer::<HashSet<String>>::new().layer(router);
+
+        let request = |permission: &str, etag: Option<&HeaderValue>| {
+            let mut request = Request::get("/orders/42?fields=status");
+            if let Some(etag) = etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+
+            let mut request = request.body(Body::empty()).unwrap();
+            request
+                .extensions_mut()
+                .insert(HashSet::from([permission.to_owned()]));
+            request
+        };
+
+        let response = app.clone().oneshot(request("orders:write", None)).await.unwrap();
+        let etag = response.headers()[ETAG].clone();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let document: Document<Value> = serde_json::from_slice(&body).unwrap();
+
+        check!(document.properties == json!({"status": "pending"}));
+        check!(document.actions.len() == 2);
+        check!(etag == document.etag().as_str());
+
+        let response = app
+            .clone()
+            .oneshot(request("orders:write", Some(&etag)))
+            .await
+            .unwrap();
+        check!(response.status() == StatusCode::NOT_MODIFIED);
+
+        // A caller with fewer permissions is sent a different document, so a different entity tag.
+        let response = app.oneshot(request("orders:read", Some(&etag))).await.unwrap();
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers()[ETAG] != etag);
+    }
+}
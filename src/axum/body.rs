@@ -0,0 +1,95 @@
+use axum::{
+    body::{boxed, BoxBody, Bytes, Full, HttpBody},
+    response::{IntoResponse, Response},
+};
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG},
+    HeaderMap,
+    HeaderValue,
+    Request,
+    StatusCode,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::Document;
+
+/// Read the entire body of a response into memory.
+pub(crate) async fn read_body(mut body: BoxBody) -> Result<Bytes, axum::Error> {
+    let mut bytes = vec![];
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk?);
+    }
+
+    Ok(Bytes::from(bytes))
+}
+
+/// Copy the method, URI, version and headers of a request, without its body.
+pub(crate) fn request_head<B>(request: &Request<B>) -> Request<()> {
+    let mut head = Request::new(());
+    *head.method_mut() = request.method().clone();
+    *head.uri_mut() = request.uri().clone();
+    *head.version_mut() = request.version();
+    *head.headers_mut() = request.headers().clone();
+
+    head
+}
+
+/// Check if the given response is a Siren document.
+pub(crate) fn is_siren(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(false, |v| v.starts_with("application/vnd.siren+json"))
+}
+
+/// Transform the Siren document in the body of the given response. If the body can not be parsed
+/// as a Siren document then the response is returned unchanged.
+pub(crate) async fn map_document<F>(response: Response, f: F) -> Response
+where
+    F: FnOnce(Document<Value>) -> Document<Value>,
+{
+    let (mut parts, body) = response.into_parts();
+
+    let bytes = match read_body(body).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let document = match serde_json::from_slice::<Document<Value>>(&bytes) {
+        Ok(document) => document,
+        Err(_) => return Response::from_parts(parts, boxed(Full::from(bytes))),
+    };
+
+    let document = f(document);
+
+    match serde_json::to_vec(&document) {
+        Ok(body) => {
+            parts.headers.remove(CONTENT_LENGTH);
+            if body != bytes {
+                refresh_representation_headers(&mut parts.headers, &document);
+            }
+
+            Response::from_parts(parts, boxed(Full::from(body)))
+        },
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// Update the headers that describe the document of a response after the document has been
+/// changed. An `ETag` is generated again from the new document, and a `Siren-Signature` is removed
+/// since the new document can not be signed here.
+pub(crate) fn refresh_representation_headers<T>(headers: &mut HeaderMap, document: &Document<T>)
+where
+    T: Serialize,
+{
+    if headers.contains_key(ETAG) {
+        match HeaderValue::from_str(&document.etag()) {
+            Ok(value) => headers.insert(ETAG, value),
+            Err(_) => headers.remove(ETAG),
+        };
+    }
+
+    headers.remove("Siren-Signature");
+}
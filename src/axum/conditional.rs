@@ -0,0 +1,525 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::{boxed, Empty, Full},
+    response::{IntoResponse, Response},
+};
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH},
+    HeaderMap, HeaderValue, Method, Request, StatusCode,
+};
+use serde_json::Value;
+use tower::{Layer, Service, ServiceExt};
+
+use super::{is_siren, lookup_request, read_body};
+use crate::{Document, ErrorDocument};
+
+/// Layer that evaluates the `If-None-Match` and `If-Match` conditional request headers against the
+/// `ETag` headers of the responses from the wrapped service.
+///
+/// - `GET` and `HEAD` requests whose `If-None-Match` header matches the entity tag of the response
+///   are answered with `304 Not Modified`.
+/// - `GET` and `HEAD` requests whose `If-Match` header does not match the entity tag of the
+///   response are answered with `412 Precondition Failed`.
+/// - Any other request with an `If-Match` or `If-None-Match` header is first evaluated against the
+///   entity tag of the response to a `GET` request for the same URI and headers, and is only
+///   passed to the wrapped service if the preconditions hold. Otherwise it is answered with
+///   `412 Precondition Failed`.
+///
+/// Entity tags are taken from the `ETag` header of the responses, for example as added by
+/// [`Response::with_etag`](crate::Response::with_etag). Alternatively, the layer can generate
+/// them for every successful Siren response that doesn't already have one by using
+/// [`ConditionalRequestLayer::with_generated_etags`].
+///
+/// As with [`AllowedMethodsLayer`](super::AllowedMethodsLayer), this layer needs to make extra
+/// `GET` requests to the wrapped service, so must wrap either an entire `Router` or the
+/// `MethodRouter` for a single route.
+///
+/// ```rust,no_run
+/// # use axum::{routing::get, Router};
+/// # use tower::Layer;
+/// # async fn get_order() -> http_siren::Response<()> { todo!() }
+/// # async fn update_order() {}
+/// let router: Router = Router::new().route("/orders/:id", get(get_order).put(update_order));
+/// let app = http_siren::axum::ConditionalRequestLayer::new()
+///     .with_generated_etags()
+///     .layer(router);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConditionalRequestLayer {
+    generate_etags: bool,
+}
+
+impl ConditionalRequestLayer {
+    /// Create a new layer, which only uses entity tags that the wrapped service provides.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate strong entity tags for successful Siren responses that don't already have an
+    /// `ETag` header, in the same way as [`Document::etag`](crate::Document::etag).
+    #[must_use]
+    pub fn with_generated_etags(mut self) -> Self {
+        self.generate_etags = true;
+
+        self
+    }
+}
+
+impl<S> Layer<S> for ConditionalRequestLayer {
+    type Service = ConditionalRequests<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConditionalRequests {
+            inner,
+            generate_etags: self.generate_etags,
+        }
+    }
+}
+
+/// Service produced by [`ConditionalRequestLayer`].
+#[derive(Debug, Clone)]
+pub struct ConditionalRequests<S> {
+    inner:          S,
+    generate_etags: bool,
+}
+
+impl<S, B> Service<Request<B>> for ConditionalRequests<S>
+where
+    S: Service<Request<B>, Response = Response> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    B: Default + Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        // The service that was driven to readiness is the one that must be called, so take it and
+        // leave a fresh clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let generate_etags = self.generate_etags;
+
+        Box::pin(async move {
+            let preconditions = Preconditions::new(request.headers());
+
+            if request.method() == Method::GET || request.method() == Method::HEAD {
+                let response = inner.call(request).await?;
+                let response = with_etag(response, generate_etags).await;
+
+                if !response.status().is_success() {
+                    return Ok(response);
+                }
+
+                // The response is successful, so there is a current representation.
+                let outcome = preconditions.evaluate(true, current_etag(&response));
+
+                return Ok(match outcome {
+                    Outcome::Proceed => response,
+                    Outcome::NotModified => not_modified_response(response),
+                    Outcome::PreconditionFailed => precondition_failed_response(),
+                });
+            }
+
+            if preconditions.is_empty() {
+                return inner.call(request).await;
+            }
+
+            let lookup = lookup_request(&request);
+            let lookup_response = inner.ready().await?.call(lookup).await?;
+            let lookup_response = with_etag(lookup_response, generate_etags).await;
+
+            let exists = lookup_response.status().is_success();
+            let etag = if exists {
+                current_etag(&lookup_response)
+            } else {
+                None
+            };
+
+            match preconditions.evaluate(exists, etag) {
+                Outcome::Proceed => inner.ready().await?.call(request).await,
+                Outcome::NotModified | Outcome::PreconditionFailed => {
+                    Ok(precondition_failed_response())
+                },
+            }
+        })
+    }
+}
+
+/// The conditional request headers of a request.
+#[derive(Debug)]
+struct Preconditions {
+    if_match:      Option<String>,
+    if_none_match: Option<String>,
+}
+
+/// The result of evaluating the preconditions of a request.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Proceed,
+    NotModified,
+    PreconditionFailed,
+}
+
+impl Preconditions {
+    /// Extract the preconditions from the given request headers.
+    fn new(headers: &HeaderMap) -> Self {
+        let join = |name| {
+            let values: Vec<_> = headers
+                .get_all(name)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .collect();
+
+            if values.is_empty() {
+                None
+            } else {
+                Some(values.join(","))
+            }
+        };
+
+        Self {
+            if_match:      join(IF_MATCH),
+            if_none_match: join(IF_NONE_MATCH),
+        }
+    }
+
+    /// Check if the request has no preconditions.
+    fn is_empty(&self) -> bool {
+        self.if_match.is_none() && self.if_none_match.is_none()
+    }
+
+    /// Evaluate the preconditions against the current representation of the resource, as
+    /// described in [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.2.2).
+    ///
+    /// # Parameters
+    /// - `exists` - Whether the resource has a current representation.
+    /// - `etag` - The entity tag of the current representation, if it has one.
+    fn evaluate(&self, exists: bool, etag: Option<&str>) -> Outcome {
+        if let Some(if_match) = &self.if_match {
+            if !matches(if_match, exists, etag, false) {
+                return Outcome::PreconditionFailed;
+            }
+        }
+
+        if let Some(if_none_match) = &self.if_none_match {
+            if matches(if_none_match, exists, etag, true) {
+                return Outcome::NotModified;
+            }
+        }
+
+        Outcome::Proceed
+    }
+}
+
+/// Check if a list of entity tags from a conditional request header matches the current
+/// representation of the resource. A header of `*` matches any current representation, whether or
+/// not it has an entity tag.
+///
+/// # Parameters
+/// - `header` - The value of the header.
+/// - `exists` - Whether the resource has a current representation.
+/// - `etag` - The entity tag of the current representation, if it has one.
+/// - `weak` - Whether to use the weak comparison function instead of the strong one.
+fn matches(header: &str, exists: bool, etag: Option<&str>, weak: bool) -> bool {
+    if header.trim() == "*" {
+        return exists;
+    }
+
+    let etag = match etag {
+        Some(etag) if exists => etag,
+        _ => return false,
+    };
+
+    let (current_weak, current) = split_etag(etag);
+
+    entity_tags(header)
+        .into_iter()
+        .any(|(candidate_weak, candidate)| {
+            candidate == current && (weak || (!candidate_weak && !current_weak))
+        })
+}
+
+/// Parse a comma-separated list of entity tags, splitting each into whether it is weak and its
+/// opaque value. Opaque values are quoted and may themselves contain commas, so the list can not
+/// simply be split on them.
+fn entity_tags(header: &str) -> Vec<(bool, &str)> {
+    let mut tags = vec![];
+    let mut rest = header;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        if rest.is_empty() {
+            return tags;
+        }
+
+        let (weak, tag) = match rest.strip_prefix("W/") {
+            Some(tag) => (true, tag),
+            None => (false, rest),
+        };
+
+        // The end of the opaque value is the closing quote, or the next comma if it is not quoted.
+        let end = match tag.strip_prefix('"') {
+            Some(quoted) => quoted.find('"').map_or(tag.len(), |index| index + 2),
+            None => tag.find(',').unwrap_or(tag.len()),
+        };
+
+        tags.push((weak, tag[..end].trim_end()));
+        rest = &tag[end..];
+    }
+}
+
+/// Split an entity tag into whether it is weak and its opaque value.
+fn split_etag(etag: &str) -> (bool, &str) {
+    let etag = etag.trim();
+
+    match etag.strip_prefix("W/") {
+        Some(opaque) => (true, opaque),
+        None => (false, etag),
+    }
+}
+
+/// Get the current entity tag from the given response.
+fn current_etag(response: &Response) -> Option<&str> {
+    response.headers().get(ETAG).and_then(|v| v.to_str().ok())
+}
+
+/// Add a generated `ETag` header to the given response if it is a successful Siren response that
+/// doesn't already have one.
+async fn with_etag(response: Response, generate: bool) -> Response {
+    if !generate
+        || !response.status().is_success()
+        || response.headers().contains_key(ETAG)
+        || !is_siren(&response)
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    let bytes = match read_body(body).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let etag = serde_json::from_slice::<Document<Value>>(&bytes)
+        .ok()
+        .and_then(|document| HeaderValue::from_str(&document.etag()).ok());
+
+    if let Some(etag) = etag {
+        parts.headers.insert(ETAG, etag);
+    }
+
+    Response::from_parts(parts, boxed(Full::from(bytes)))
+}
+
+/// Build a `304 Not Modified` response from the full response to the request.
+fn not_modified_response(response: Response) -> Response {
+    let (mut parts, _) = response.into_parts();
+
+    parts.status = StatusCode::NOT_MODIFIED;
+    parts.headers.remove(CONTENT_TYPE);
+    parts.headers.remove(CONTENT_LENGTH);
+
+    Response::from_parts(parts, boxed(Empty::new()))
+}
+
+/// Build a Siren `412 Precondition Failed` response.
+fn precondition_failed_response() -> Response {
+    ErrorDocument::new(StatusCode::PRECONDITION_FAILED)
+        .with_detail("The preconditions of the request do not match the current resource")
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{body::Body, routing::get, Router};
+    use serde_json::json;
+
+    use super::*;
+
+    async fn get_order() -> crate::Response<Value> {
+        crate::Response::new(Document::new(json!({"orderNumber": 42}))).with_etag()
+    }
+
+    async fn get_customer() -> crate::Response<Value> {
+        Document::new(json!({"customerId": "pj123"})).into()
+    }
+
+    async fn update() -> StatusCode {
+        StatusCode::NO_CONTENT
+    }
+
+    fn etag() -> String {
+        Document::new(json!({"orderNumber": 42})).etag()
+    }
+
+    async fn send(layer: ConditionalRequestLayer, request: Request<Body>) -> Response {
+        let router: Router = Router::new()
+            .route("/orders/42", get(get_order).put(update))
+            .route("/customers/pj123", get(get_customer).put(update));
+
+        layer.layer(router).oneshot(request).await.unwrap()
+    }
+
+    fn request(method: Method, uri: &str, header: Option<(&str, &str)>) -> Request<Body> {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some((name, value)) = header {
+            request = request.header(name, value);
+        }
+
+        request.body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn evaluate_preconditions() {
+        let preconditions = |if_match: Option<&str>, if_none_match: Option<&str>| Preconditions {
+            if_match:      if_match.map(ToOwned::to_owned),
+            if_none_match: if_none_match.map(ToOwned::to_owned),
+        };
+
+        check!(preconditions(None, None).evaluate(true, Some("\"a\"")) == Outcome::Proceed);
+        check!(
+            preconditions(Some("\"a\""), None).evaluate(true, Some("\"a\"")) == Outcome::Proceed
+        );
+        check!(
+            preconditions(Some("\"b\", \"c\""), None).evaluate(true, Some("\"a\""))
+                == Outcome::PreconditionFailed
+        );
+        check!(
+            preconditions(Some("W/\"a\""), None).evaluate(true, Some("\"a\""))
+                == Outcome::PreconditionFailed
+        );
+        check!(preconditions(Some("*"), None).evaluate(false, None) == Outcome::PreconditionFailed);
+        check!(preconditions(Some("*"), None).evaluate(true, None) == Outcome::Proceed);
+        check!(
+            preconditions(None, Some("W/\"a\"")).evaluate(true, Some("\"a\""))
+                == Outcome::NotModified
+        );
+        check!(preconditions(None, Some("*")).evaluate(false, None) == Outcome::Proceed);
+        check!(preconditions(None, Some("*")).evaluate(true, None) == Outcome::NotModified);
+    }
+
+    #[test]
+    fn entity_tags_with_commas() {
+        check!(
+            entity_tags(" \"a,b\", W/\"c\" ,,\"\"")
+                == vec![(false, "\"a,b\""), (true, "\"c\""), (false, "\"\"")]
+        );
+        check!(matches("\"x\", \"a,b\"", true, Some("\"a,b\""), false));
+        check!(!matches("\"a,b\"", true, Some("\"a\""), true));
+        check!(!matches("\"a,b\"", true, Some("\"b\""), true));
+    }
+
+    #[tokio::test]
+    async fn not_modified() {
+        let etag = etag();
+        let response = send(
+            ConditionalRequestLayer::new(),
+            request(Method::GET, "/orders/42", Some(("If-None-Match", &etag))),
+        )
+        .await;
+
+        check!(response.status() == StatusCode::NOT_MODIFIED);
+        check!(response.headers().get(ETAG).unwrap() == etag.as_str());
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        check!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn modified() {
+        let response = send(
+            ConditionalRequestLayer::new(),
+            request(Method::GET, "/orders/42", Some(("If-None-Match", "\"other\""))),
+        )
+        .await;
+
+        check!(response.status() == StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn update_with_matching_etag() {
+        let etag = etag();
+        let response = send(
+            ConditionalRequestLayer::new(),
+            request(Method::PUT, "/orders/42", Some(("If-Match", &etag))),
+        )
+        .await;
+
+        check!(response.status() == StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn update_with_stale_etag() {
+        let response = send(
+            ConditionalRequestLayer::new(),
+            request(Method::PUT, "/orders/42", Some(("If-Match", "\"stale\""))),
+        )
+        .await;
+
+        check!(response.status() == StatusCode::PRECONDITION_FAILED);
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/vnd.siren+json");
+    }
+
+    #[tokio::test]
+    async fn wildcard_without_etag() {
+        let response = send(
+            ConditionalRequestLayer::new(),
+            request(Method::PUT, "/customers/pj123", Some(("If-Match", "*"))),
+        )
+        .await;
+        check!(response.status() == StatusCode::NO_CONTENT);
+
+        let response = send(
+            ConditionalRequestLayer::new(),
+            request(Method::PUT, "/customers/pj123", Some(("If-None-Match", "*"))),
+        )
+        .await;
+        check!(response.status() == StatusCode::PRECONDITION_FAILED);
+
+        let response = send(
+            ConditionalRequestLayer::new(),
+            request(Method::PUT, "/customers/new", Some(("If-Match", "*"))),
+        )
+        .await;
+        check!(response.status() == StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[tokio::test]
+    async fn generated_etags() {
+        let response = send(
+            ConditionalRequestLayer::new(),
+            request(Method::GET, "/customers/pj123", None),
+        )
+        .await;
+        check!(response.headers().get(ETAG) == None);
+
+        let response = send(
+            ConditionalRequestLayer::new().with_generated_etags(),
+            request(Method::GET, "/customers/pj123", None),
+        )
+        .await;
+        let etag = Document::new(json!({"customerId": "pj123"})).etag();
+        check!(response.headers().get(ETAG).unwrap() == etag.as_str());
+
+        let response = send(
+            ConditionalRequestLayer::new().with_generated_etags(),
+            request(Method::PUT, "/customers/pj123", Some(("If-Match", &etag))),
+        )
+        .await;
+        check!(response.status() == StatusCode::NO_CONTENT);
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    fmt::Display,
+    ops::{Deref, DerefMut},
+};
+
+use axum::{
+    async_trait,
+    extract::{FromRequest, RequestParts},
+    response::{IntoResponse, Response},
+};
+
+use crate::ErrorDocument;
+
+impl IntoResponse for ErrorDocument {
+    fn into_response(self) -> Response {
+        crate::Response::from(self).into_response()
+    }
+}
+
+impl ErrorDocument {
+    /// Create an error document from the rejection of an Axum extractor. The status code is the
+    /// one that Axum would have responded with, and the detail is the message of the rejection.
+    ///
+    /// # Parameters
+    /// - `rejection` - The rejection to convert.
+    pub fn from_rejection<R>(rejection: R) -> Self
+    where
+        R: IntoResponse + Display,
+    {
+        let detail = rejection.to_string();
+        let status_code = rejection.into_response().status();
+
+        Self::new(status_code).with_detail(detail)
+    }
+}
+
+/// Wrapper around an Axum extractor that renders any rejection as a Siren error document instead
+/// of the plain text response that Axum would otherwise produce.
+///
+/// ```rust
+/// # use axum::Json;
+/// # use http_siren::axum::WithSirenRejection;
+/// async fn create_order(
+///     WithSirenRejection(Json(order)): WithSirenRejection<Json<serde_json::Value>>,
+/// ) {
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WithSirenRejection<E>(pub E);
+
+#[async_trait]
+impl<B, E> FromRequest<B> for WithSirenRejection<E>
+where
+    B: Send,
+    E: FromRequest<B>,
+    E::Rejection: Display,
+{
+    type Rejection = ErrorDocument;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        E::from_request(req)
+            .await
+            .map(Self)
+            .map_err(ErrorDocument::from_rejection)
+    }
+}
+
+impl<E> Deref for WithSirenRejection<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for WithSirenRejection<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{body::Body, routing::post, Json, Router};
+    use http::{Request, StatusCode};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn create_order(
+        WithSirenRejection(Json(order)): WithSirenRejection<Json<Value>>,
+    ) -> Json<Value> {
+        Json(order)
+    }
+
+    async fn send(content_type: &str, body: &'static str) -> Response {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/orders")
+            .header("Content-Type", content_type)
+            .body(Body::from(body))
+            .unwrap();
+
+        Router::new()
+            .route("/orders", post(create_order))
+            .oneshot(request)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn error_document() {
+        let response = ErrorDocument::new(StatusCode::CONFLICT)
+            .with_detail("Order already exists")
+            .into_response();
+
+        check!(response.status() == StatusCode::CONFLICT);
+        check!(response.headers().get("Content-Type").unwrap() == "application/vnd.siren+json");
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        check!(body["class"] == json!(["error"]));
+        check!(body["properties"]["status"] == json!(409));
+        check!(body["properties"]["detail"] == json!("Order already exists"));
+    }
+
+    #[tokio::test]
+    async fn extractor_success() {
+        let response = send("application/json", r#"{"orderNumber": 42}"#).await;
+
+        check!(response.status() == StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn extractor_rejection() {
+        let response = send("text/plain", r#"{"orderNumber": 42}"#).await;
+
+        check!(response.status() == StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        check!(response.headers().get("Content-Type").unwrap() == "application/vnd.siren+json");
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        check!(body["class"] == json!(["error"]));
+        check!(body["properties"]["status"] == json!(415));
+        check!(body["properties"]["title"] == json!("Unsupported Media Type"));
+        check!(body["properties"]["detail"].is_string());
+    }
+}
@@ -0,0 +1,286 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::response::{IntoResponse, Response};
+use http::{header::ALLOW, HeaderValue, Method, Request, StatusCode};
+use serde::Serialize;
+use tower::{Layer, Service, ServiceExt};
+
+use crate::{document::allowed_methods, Action, Document, ErrorDocument, Link};
+
+/// Details of the resource that a Siren response represents, stored in the extensions of the
+/// rendered response so that [`AllowedMethodsLayer`] can make use of them.
+#[derive(Debug, Clone)]
+pub(crate) struct ResourceActions {
+    self_link: Option<Link>,
+    actions:   Vec<Action>,
+}
+
+impl ResourceActions {
+    /// Build the resource actions for the given document.
+    pub(crate) fn new<T>(document: &Document<T>) -> Self
+    where
+        T: Serialize,
+    {
+        Self {
+            self_link: document.self_link().cloned(),
+            actions:   document.resource_actions().into_iter().cloned().collect(),
+        }
+    }
+
+    /// Build the value of the `Allow` header for the resource. This is every method that the
+    /// resource allows, plus `OPTIONS` since the layer answers those requests.
+    fn allow_header(&self) -> HeaderValue {
+        let mut methods = allowed_methods(&self.actions);
+        if !methods.contains(&Method::OPTIONS) {
+            methods.push(Method::OPTIONS);
+        }
+
+        let allow = methods
+            .iter()
+            .map(Method::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        HeaderValue::from_str(&allow).expect("HTTP methods are always valid header values")
+    }
+}
+
+/// Layer to apply to Axum routes that return Siren responses, that uses the actions on the
+/// document to answer requests about which HTTP methods are allowed.
+///
+/// - `OPTIONS` requests are answered with a `204 No Content` response with an `Allow` header
+///   listing `GET`, plus every method of the actions that target the same URL as the document's
+///   self link.
+/// - `405 Method Not Allowed` responses are replaced with a Siren document that includes the
+///   actions that are valid for the resource, along with the same `Allow` header.
+///
+/// Both of these work by making a `GET` request to the wrapped service for the same URI and
+/// headers, and inspecting the Siren document that it returns. Extensions on the original request
+/// are not available to this `GET` request, unless the request was an `OPTIONS` request.
+///
+/// Because `Router::layer` applies layers to each HTTP method of a route separately, this layer
+/// must instead wrap either an entire `Router` or the `MethodRouter` for a single route:
+///
+/// ```rust,no_run
+/// # use axum::{routing::get, Router};
+/// # use tower::Layer;
+/// # async fn get_order() -> http_siren::Response<()> { todo!() }
+/// # async fn delete_order() {}
+/// let router: Router = Router::new().route("/orders/:id", get(get_order).delete(delete_order));
+/// let app = http_siren::axum::AllowedMethodsLayer.layer(router);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowedMethodsLayer;
+
+impl<S> Layer<S> for AllowedMethodsLayer {
+    type Service = AllowedMethods<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AllowedMethods { inner }
+    }
+}
+
+/// Service produced by [`AllowedMethodsLayer`].
+#[derive(Debug, Clone)]
+pub struct AllowedMethods<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for AllowedMethods<S>
+where
+    S: Service<Request<B>, Response = Response> + Clone + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+    B: Default + Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        // The service that was driven to readiness is the one that must be called, so take it and
+        // leave a fresh clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            if request.method() == Method::OPTIONS {
+                *request.method_mut() = Method::GET;
+
+                let response = inner.call(request).await?;
+                return Ok(options_response(response));
+            }
+
+            let method = request.method().clone();
+            let lookup = lookup_request(&request);
+
+            let response = inner.call(request).await?;
+            if response.status() != StatusCode::METHOD_NOT_ALLOWED {
+                return Ok(response);
+            }
+
+            let lookup_response = inner.ready().await?.call(lookup).await?;
+            match lookup_response.extensions().get::<ResourceActions>() {
+                Some(resource) => Ok(method_not_allowed_response(&method, resource)),
+                None => Ok(response),
+            }
+        })
+    }
+}
+
+/// Build a `GET` request for the same URI and headers as the given request.
+pub(crate) fn lookup_request<B>(request: &Request<B>) -> Request<B>
+where
+    B: Default,
+{
+    let mut lookup = Request::new(B::default());
+    *lookup.method_mut() = Method::GET;
+    *lookup.uri_mut() = request.uri().clone();
+    *lookup.version_mut() = request.version();
+    *lookup.headers_mut() = request.headers().clone();
+
+    lookup
+}
+
+/// Build the response to an `OPTIONS` request from the response to the equivalent `GET` request.
+fn options_response(response: Response) -> Response {
+    if !response.status().is_success() {
+        return response;
+    }
+
+    let allow = match response.extensions().get::<ResourceActions>() {
+        Some(resource) => resource.allow_header(),
+        None => HeaderValue::from_static("GET, OPTIONS"),
+    };
+
+    (StatusCode::NO_CONTENT, [(ALLOW, allow)]).into_response()
+}
+
+/// Build a Siren `405 Method Not Allowed` response for the given resource.
+fn method_not_allowed_response(method: &Method, resource: &ResourceActions) -> Response {
+    let mut error = ErrorDocument::new(StatusCode::METHOD_NOT_ALLOWED).with_detail(format!(
+        "The method {method} is not allowed for this resource"
+    ));
+
+    if let Some(link) = &resource.self_link {
+        error = error.with_link(link.clone());
+    }
+    error.document.actions.clone_from(&resource.actions);
+
+    let mut response = error.into_response();
+    response
+        .headers_mut()
+        .insert(ALLOW, resource.allow_header());
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{body::Body, routing::get, Router};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::values::LinkRelation;
+
+    async fn get_order() -> crate::Response<Value> {
+        Document::new(json!({"orderNumber": 42}))
+            .with_link(Link::new("/orders/42").with_rel(LinkRelation::SelfLink))
+            .with_action(Action::new("update", "/orders/42").with_method(Method::PUT))
+            .with_action(Action::new("add-item", "/orders/42/items").with_method(Method::POST))
+            .with_action(Action::new("delete", "/orders/42").with_method(Method::DELETE))
+            .into()
+    }
+
+    async fn update_order() -> StatusCode {
+        StatusCode::NO_CONTENT
+    }
+
+    fn router() -> AllowedMethods<Router> {
+        AllowedMethodsLayer.layer(
+            Router::new()
+                .route("/orders/42", get(get_order).put(update_order))
+                .route("/plain", get(update_order)),
+        )
+    }
+
+    async fn send(method: Method, uri: &str) -> Response {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+
+        router().oneshot(request).await.unwrap().into_response()
+    }
+
+    #[tokio::test]
+    async fn options() {
+        let response = send(Method::OPTIONS, "/orders/42").await;
+
+        check!(response.status() == StatusCode::NO_CONTENT);
+        check!(response.headers().get(ALLOW).unwrap() == "GET, PUT, DELETE, OPTIONS");
+    }
+
+    #[tokio::test]
+    async fn options_not_siren() {
+        let response = send(Method::OPTIONS, "/plain").await;
+
+        check!(response.status() == StatusCode::NO_CONTENT);
+        check!(response.headers().get(ALLOW).unwrap() == "GET, OPTIONS");
+    }
+
+    #[tokio::test]
+    async fn options_not_found() {
+        let response = send(Method::OPTIONS, "/orders/43").await;
+
+        check!(response.status() == StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn allowed_method() {
+        let response = send(Method::PUT, "/orders/42").await;
+
+        check!(response.status() == StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn method_not_allowed() {
+        let response = send(Method::PATCH, "/orders/42").await;
+
+        check!(response.status() == StatusCode::METHOD_NOT_ALLOWED);
+        check!(response.headers().get(ALLOW).unwrap() == "GET, PUT, DELETE, OPTIONS");
+        check!(response.headers().get("Content-Type").unwrap() == "application/vnd.siren+json");
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        check!(body["class"] == json!(["error"]));
+        check!(body["properties"]["status"] == json!(405));
+        check!(
+            body["properties"]["detail"] == json!("The method PATCH is not allowed for this resource")
+        );
+        check!(body["links"][0]["href"] == json!("/orders/42"));
+        check!(body["actions"].as_array().unwrap().len() == 2);
+        check!(body["actions"][0]["name"] == json!("update"));
+        check!(body["actions"][1]["name"] == json!("delete"));
+    }
+
+    #[tokio::test]
+    async fn method_not_allowed_not_siren() {
+        let response = send(Method::PATCH, "/plain").await;
+
+        check!(response.status() == StatusCode::METHOD_NOT_ALLOWED);
+        check!(response.headers().get("Content-Type").is_none());
+    }
+}
@@ -0,0 +1,567 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::Infallible,
+    fs,
+    io,
+    path::Path,
+    sync::Arc,
+};
+
+use axum::{
+    body::{boxed, Body},
+    response::{IntoResponse, Response},
+    Router,
+};
+use http::{
+    header::{CONTENT_TYPE, HOST},
+    Method,
+    Request,
+    StatusCode,
+};
+use serde_json::{Map, Value};
+use tower::service_fn;
+
+use super::read_body;
+use crate::{uri, Action, Document, Entity, ErrorDocument, Link};
+
+/// Builder for a mock Siren API that serves fixture documents, for developing clients without the
+/// real API.
+///
+/// - `GET` requests for the path of a fixture are answered with that fixture.
+/// - Requests that match the href and method of an action in any fixture are treated as
+///   submissions of that action. Any query string of the href is ignored, and relative hrefs are
+///   resolved against the path of the fixture. The submission, either JSON or form encoded, is
+///   validated against the fields of the action, failing with a `422 Unprocessable Entity` if it
+///   breaks their constraints. Otherwise the response is the follow-up document configured for the
+///   action, or `204 No Content` if there is none.
+/// - Absolute hrefs in the documents served are rewritten to the origin of the mock, so that
+///   fixtures captured from a real API link back to the mock instead.
+///
+/// ```rust,no_run
+/// # use axum::Router;
+/// # use http_siren::{axum::MockServer, Document};
+/// # use serde_json::json;
+/// # fn example() -> std::io::Result<()> {
+/// let app: Router = MockServer::new()
+///     .with_fixture_dir("fixtures")?
+///     .with_follow_up("add-item", Document::new(json!({"itemCount": 4})).with_class("order"))
+///     .into_router();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockServer {
+    fixtures:   BTreeMap<String, Document<Value>>,
+    follow_ups: HashMap<String, Document<Value>>,
+    origin:     Option<String>,
+}
+
+impl MockServer {
+    /// Create a new mock, with no fixtures.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify a fixture to serve.
+    ///
+    /// # Parameters
+    /// - `path` - The path to serve the fixture from.
+    /// - `document` - The fixture.
+    #[must_use]
+    pub fn with_fixture<P>(mut self, path: P, document: Document<Value>) -> Self
+    where
+        P: ToString,
+    {
+        self.fixtures.insert(path.to_string(), document);
+
+        self
+    }
+
+    /// Load every JSON file in a directory and its subdirectories as a fixture. Each fixture is
+    /// served from its path relative to the directory, without the `.json` extension, and fixtures
+    /// named `index.json` are served from the path of the directory that they are in. For example,
+    /// `orders/42.json` is served from `/orders/42` and `orders/index.json` from `/orders`.
+    ///
+    /// # Parameters
+    /// - `dir` - The directory to load the fixtures from.
+    ///
+    /// # Errors
+    /// If the directory can't be read, or any JSON file in it is not a Siren document.
+    pub fn with_fixture_dir<P>(mut self, dir: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        load_fixtures(dir.as_ref(), "", &mut self.fixtures)?;
+
+        Ok(self)
+    }
+
+    /// Specify the document to respond with when an action is successfully submitted.
+    ///
+    /// # Parameters
+    /// - `action` - The name of the action.
+    /// - `document` - The document to respond with.
+    #[must_use]
+    pub fn with_follow_up<A>(mut self, action: A, document: Document<Value>) -> Self
+    where
+        A: ToString,
+    {
+        self.follow_ups.insert(action.to_string(), document);
+
+        self
+    }
+
+    /// Specify the origin to rewrite absolute hrefs to. By default, this is worked out from the
+    /// `Host` header of each request.
+    ///
+    /// # Parameters
+    /// - `origin` - The origin, such as `http://localhost:3000`.
+    #[must_use]
+    pub fn with_origin<O>(mut self, origin: O) -> Self
+    where
+        O: ToString,
+    {
+        self.origin = Some(origin.to_string().trim_end_matches('/').to_owned());
+
+        self
+    }
+
+    /// Build the router that serves the mock.
+    #[must_use]
+    pub fn into_router(self) -> Router {
+        let mock = Arc::new(self);
+
+        Router::new().fallback(service_fn(move |request: Request<Body>| {
+            let mock = mock.clone();
+            async move { Ok::<_, Infallible>(mock.handle(request).await) }
+        }))
+    }
+
+    /// Answer a single request to the mock.
+    async fn handle(&self, request: Request<Body>) -> Response {
+        let origin = self.origin.clone().or_else(|| {
+            request
+                .headers()
+                .get(HOST)
+                .and_then(|host| host.to_str().ok())
+                .map(|host| format!("http://{host}"))
+        });
+        let path = request.uri().path().to_owned();
+        let method = request.method().clone();
+
+        if method == Method::GET {
+            if let Some(fixture) = self.fixtures.get(&path) {
+                return crate::Response::from(rewrite(fixture.clone(), origin.as_deref()))
+                    .into_response();
+            }
+        }
+
+        let action = match self.find_action(&method, &path) {
+            Some(action) => action,
+            None => {
+                return if self.fixtures.contains_key(&path) {
+                    ErrorDocument::new(StatusCode::METHOD_NOT_ALLOWED).into_response()
+                } else {
+                    ErrorDocument::new(StatusCode::NOT_FOUND).into_response()
+                };
+            },
+        };
+
+        let submission = match parse_submission(request).await {
+            Ok(submission) => submission,
+            Err(error) => return error.into_response(),
+        };
+        if let Err(error) = action.validate(&submission) {
+            return ErrorDocument::from(error).into_response();
+        }
+
+        match self.follow_ups.get(&action.name) {
+            Some(document) => {
+                crate::Response::from(rewrite(document.clone(), origin.as_deref())).into_response()
+            },
+            None => StatusCode::NO_CONTENT.into_response(),
+        }
+    }
+
+    /// Find the action, in any fixture, that is submitted with the given method to the given path.
+    fn find_action(&self, method: &Method, path: &str) -> Option<&Action> {
+        self.fixtures
+            .iter()
+            .flat_map(|(fixture, document)| {
+                all_actions(document)
+                    .into_iter()
+                    .map(move |action| (fixture, action))
+            })
+            .find(|(fixture, action)| {
+                action.method() == method && action_path(fixture, &action.href) == path
+            })
+            .map(|(_, action)| action)
+    }
+}
+
+/// Load the fixtures from a directory, serving them from paths under the given prefix.
+fn load_fixtures(
+    dir: &Path,
+    prefix: &str,
+    fixtures: &mut BTreeMap<String, Document<Value>>,
+) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(fs::DirEntry::file_name);
+
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        if path.is_dir() {
+            load_fixtures(&path, &format!("{prefix}/{name}"), fixtures)?;
+        } else if let Some(stem) = name.strip_suffix(".json") {
+            let contents = fs::read(&path)?;
+            let document = serde_json::from_slice(&contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {e}", path.display()),
+                )
+            })?;
+
+            let served = match stem {
+                "index" if prefix.is_empty() => "/".to_owned(),
+                "index" => prefix.to_owned(),
+                stem => format!("{prefix}/{stem}"),
+            };
+            fixtures.insert(served, document);
+        }
+    }
+
+    Ok(())
+}
+
+/// Every action of a document, including those of its embedded representations.
+fn all_actions(document: &Document<Value>) -> Vec<&Action> {
+    fn collect<'a>(entities: &'a [Entity], actions: &mut Vec<&'a Action>) {
+        for entity in entities {
+            if let Entity::Representation(representation) = entity {
+                actions.extend(&representation.actions);
+                collect(&representation.entities, actions);
+            }
+        }
+    }
+
+    let mut actions = document.actions.iter().collect();
+    collect(&document.entities, &mut actions);
+
+    actions
+}
+
+/// Parse the body of an action submission, either as JSON or as a form.
+async fn parse_submission(request: Request<Body>) -> Result<Value, ErrorDocument> {
+    let json = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(false, |v| {
+            v.starts_with("application/json") || v.contains("+json")
+        });
+
+    let body = read_body(boxed(request.into_body())).await.map_err(|e| {
+        ErrorDocument::new(StatusCode::BAD_REQUEST)
+            .with_detail(format!("Failed to read request body: {e}"))
+    })?;
+
+    if json {
+        return serde_json::from_slice(&body).map_err(|e| {
+            ErrorDocument::new(StatusCode::BAD_REQUEST)
+                .with_detail(format!("Failed to parse request body: {e}"))
+        });
+    }
+
+    let pairs: Vec<(String, String)> = serde_urlencoded::from_bytes(&body).map_err(|e| {
+        ErrorDocument::new(StatusCode::BAD_REQUEST)
+            .with_detail(format!("Failed to parse request body: {e}"))
+    })?;
+
+    // Fields that are submitted several times become arrays of their values.
+    let mut submission = Map::new();
+    for (name, value) in pairs {
+        match submission.get_mut(&name) {
+            None => {
+                submission.insert(name, Value::String(value));
+            },
+            Some(Value::Array(values)) => values.push(Value::String(value)),
+            Some(existing) => {
+                *existing = Value::Array(vec![existing.take(), Value::String(value)]);
+            },
+        }
+    }
+
+    Ok(Value::Object(submission))
+}
+
+/// Rewrite every absolute href in a document to the given origin.
+fn rewrite(mut document: Document<Value>, origin: Option<&str>) -> Document<Value> {
+    let origin = match origin {
+        Some(origin) => origin,
+        None => return document,
+    };
+
+    rewrite_links(&mut document.links, origin);
+    rewrite_entities(&mut document.entities, origin);
+    rewrite_actions(&mut document.actions, origin);
+
+    document
+}
+
+fn rewrite_entities(entities: &mut [Entity], origin: &str) {
+    for entity in entities {
+        match entity {
+            Entity::Link(link) => link.href = rewrite_href(&link.href, origin),
+            Entity::Representation(representation) => {
+                rewrite_links(&mut representation.links, origin);
+                rewrite_entities(&mut representation.entities, origin);
+                rewrite_actions(&mut representation.actions, origin);
+            },
+        }
+    }
+}
+
+fn rewrite_links(links: &mut [Link], origin: &str) {
+    for link in links {
+        link.href = rewrite_href(&link.href, origin);
+    }
+}
+
+fn rewrite_actions(actions: &mut [Action], origin: &str) {
+    for action in actions {
+        action.href = rewrite_href(&action.href, origin);
+    }
+}
+
+/// Replace the origin of an absolute href. Relative hrefs are left as they are.
+fn rewrite_href(href: &str, origin: &str) -> String {
+    if href.contains("://") {
+        format!("{origin}{}", path_of(href))
+    } else {
+        href.to_owned()
+    }
+}
+
+/// The path of an href, along with any query string, without the origin.
+fn path_of(href: &str) -> &str {
+    let scheme = match href.find("://") {
+        Some(scheme) => scheme,
+        None => return href,
+    };
+
+    let authority = &href[scheme + 3..];
+    match authority.find('/') {
+        Some(path) => &authority[path..],
+        None => "/",
+    }
+}
+
+/// The path that an action with the given href is submitted to, without any query string or
+/// fragment. Relative hrefs are resolved against the path of the fixture that the action is in.
+fn action_path(fixture: &str, href: &str) -> String {
+    let resolved = uri::resolve(fixture, href);
+    match uri::Reference::parse(&resolved).path {
+        "" => "/".to_owned(),
+        path => path.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::Field;
+
+    fn mock() -> MockServer {
+        MockServer::new()
+            .with_fixture(
+                "/orders/42",
+                Document::new(json!({"orderNumber": 42}))
+                    .with_class("order")
+                    .with_link(Link::new("https://api.x.io/orders/42").with_rel("self"))
+                    .with_embedded_link(Link::new("/orders/42/items").with_rel("items"))
+                    .with_action(
+                        Action::new("add-item", "https://api.x.io/orders/42/items")
+                            .with_method(Method::POST)
+                            .with_field(Field::new("productCode").with_required())
+                            .with_field(Field::new("quantity").with_type("number").with_min(1.0)),
+                    ),
+            )
+            .with_follow_up(
+                "add-item",
+                Document::new(json!({"itemCount": 4}))
+                    .with_link(Link::new("https://api.x.io/orders/42").with_rel("order")),
+            )
+    }
+
+    async fn send(mock: MockServer, request: Request<Body>) -> (StatusCode, Value) {
+        let response = mock.into_router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn serve_fixture() {
+        let request = Request::get("/orders/42")
+            .header(HOST, "localhost:3000")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(mock(), request).await;
+
+        check!(status == StatusCode::OK);
+        check!(body["properties"] == json!({"orderNumber": 42}));
+        check!(body["links"][0]["href"] == json!("http://localhost:3000/orders/42"));
+        check!(body["entities"][0]["href"] == json!("/orders/42/items"));
+        check!(body["actions"][0]["href"] == json!("http://localhost:3000/orders/42/items"));
+    }
+
+    #[tokio::test]
+    async fn fixed_origin() {
+        let request = Request::get("/orders/42").body(Body::empty()).unwrap();
+        let (_, body) = send(mock().with_origin("https://mock.x.io/"), request).await;
+
+        check!(body["links"][0]["href"] == json!("https://mock.x.io/orders/42"));
+    }
+
+    #[tokio::test]
+    async fn not_found() {
+        let request = Request::get("/orders/43").body(Body::empty()).unwrap();
+        let (status, body) = send(mock(), request).await;
+
+        check!(status == StatusCode::NOT_FOUND);
+        check!(body["class"] == json!(["error"]));
+
+        let request = Request::delete("/orders/42").body(Body::empty()).unwrap();
+        let (status, _) = send(mock(), request).await;
+
+        check!(status == StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn submit_form() {
+        let request = Request::post("/orders/42/items")
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(HOST, "localhost:3000")
+            .body(Body::from("productCode=abc&quantity=2"))
+            .unwrap();
+        let (status, body) = send(mock(), request).await;
+
+        check!(status == StatusCode::OK);
+        check!(body["properties"] == json!({"itemCount": 4}));
+        check!(body["links"][0]["href"] == json!("http://localhost:3000/orders/42"));
+    }
+
+    #[tokio::test]
+    async fn submit_invalid_json() {
+        let request = Request::post("/orders/42/items")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"quantity": 0}"#))
+            .unwrap();
+        let (status, body) = send(mock(), request).await;
+
+        check!(status == StatusCode::UNPROCESSABLE_ENTITY);
+        check!(
+            body["properties"]["detail"]
+                == json!("productCode is required, quantity must be at least 1")
+        );
+    }
+
+    #[tokio::test]
+    async fn submit_with_query_and_relative_hrefs() {
+        let mock = MockServer::new().with_fixture(
+            "/customers/pj123",
+            Document::new(json!({"customerId": "pj123"}))
+                .with_action(
+                    Action::new(
+                        "add-note",
+                        "https://api.x.io/customers/pj123/notes?source=web",
+                    )
+                    .with_method(Method::POST),
+                )
+                .with_action(Action::new("rename", "pj123/name").with_method(Method::PUT))
+                .with_action(
+                    Action::new("create-order", "../orders?customer=pj123")
+                        .with_method(Method::POST),
+                ),
+        );
+
+        for (method, uri) in [
+            (Method::POST, "/customers/pj123/notes"),
+            (Method::PUT, "/customers/pj123/name"),
+            (Method::POST, "/orders"),
+        ] {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from("{}"))
+                .unwrap();
+            let (status, _) = send(mock.clone(), request).await;
+
+            check!(status == StatusCode::NO_CONTENT, "{uri}");
+        }
+    }
+
+    #[test]
+    fn action_paths() {
+        check!(
+            action_path("/orders/42", "https://api.x.io/orders/42/items?a=1") == "/orders/42/items"
+        );
+        check!(action_path("/orders/42", "items#top") == "/orders/items");
+        check!(action_path("/orders/42", "./") == "/orders/");
+        check!(action_path("/orders/42", "../customers/./pj123") == "/customers/pj123");
+        check!(action_path("/orders/42", "..") == "/");
+        check!(action_path("/orders/42", "?page=2") == "/orders/42");
+    }
+
+    #[tokio::test]
+    async fn no_follow_up() {
+        let request = Request::post("/orders/42/items")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"productCode": "abc"}"#))
+            .unwrap();
+        let fixture = mock().fixtures["/orders/42"].clone();
+        let (status, _) = send(MockServer::new().with_fixture("/", fixture), request).await;
+
+        check!(status == StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn fixture_dir() {
+        let dir = std::env::temp_dir().join(format!("http_siren_mock_{}", std::process::id()));
+        fs::create_dir_all(dir.join("orders")).unwrap();
+        fs::write(dir.join("index.json"), r#"{"class": ["root"], "properties": {}}"#).unwrap();
+        let orders = r#"{"class": ["orders"], "properties": {}}"#;
+        fs::write(dir.join("orders/index.json"), orders).unwrap();
+        fs::write(dir.join("orders/42.json"), r#"{"class": ["order"], "properties": {}}"#).unwrap();
+        fs::write(dir.join("README.md"), "Not a fixture").unwrap();
+
+        let mock = MockServer::new().with_fixture_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        check!(mock.fixtures.keys().collect::<Vec<_>>() == vec!["/", "/orders", "/orders/42"]);
+
+        let request = Request::get("/orders").body(Body::empty()).unwrap();
+        let (_, body) = send(mock, request).await;
+        check!(body["class"] == json!(["orders"]));
+    }
+
+    #[test]
+    fn invalid_fixture_dir() {
+        let error = MockServer::new()
+            .with_fixture_dir("/does/not/exist")
+            .unwrap_err();
+
+        check!(error.kind() == io::ErrorKind::NotFound);
+    }
+}
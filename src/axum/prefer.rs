@@ -0,0 +1,279 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{
+    body::{boxed, Empty, Full},
+    response::{IntoResponse, Response},
+};
+use http::{
+    header::{HeaderName, CONTENT_LENGTH, CONTENT_TYPE, LOCATION, VARY},
+    HeaderValue, Request, StatusCode,
+};
+use serde_json::Value;
+use tower::{Layer, Service};
+
+use super::{is_siren, map_document, read_body};
+use crate::{Document, ReturnPreference};
+
+/// The name of the header used to indicate which preferences were honoured.
+const PREFERENCE_APPLIED: HeaderName = HeaderName::from_static("preference-applied");
+
+/// How a Siren response should be reduced when the client asks for `Prefer: return=minimal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimalResponse {
+    /// Return the document without any embedded entities or actions.
+    Reduced,
+    /// Return a `204 No Content` response with a `Location` header derived from the self link of
+    /// the document. Documents without a self link are reduced instead.
+    NoContent,
+}
+
+impl Default for MinimalResponse {
+    fn default() -> Self {
+        Self::Reduced
+    }
+}
+
+/// Layer that honours the `return` preference of the `Prefer` header from
+/// [RFC 7240](https://www.rfc-editor.org/rfc/rfc7240) for the Siren documents returned by the
+/// wrapped service.
+///
+/// - `Prefer: return=minimal` reduces successful responses as described by the
+///   [`MinimalResponse`] that the layer was configured with.
+/// - `Prefer: return=representation` leaves responses unchanged, since they already contain the
+///   full representation.
+///
+/// In both cases the applied preference is echoed back in the `Preference-Applied` header. Every
+/// Siren response also gets `Prefer` added to its `Vary` header, since the content depends on it.
+/// Responses that are not Siren documents, and unsuccessful responses, are passed through
+/// untouched.
+///
+/// ```rust,no_run
+/// # use axum::{routing::get, Router};
+/// # use http_siren::axum::{MinimalResponse, PreferLayer};
+/// # async fn get_order() -> http_siren::Response<()> { todo!() }
+/// let app: Router = Router::new()
+///     .route("/orders/:id", get(get_order))
+///     .layer(PreferLayer::new().with_minimal_response(MinimalResponse::NoContent));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferLayer {
+    minimal: MinimalResponse,
+}
+
+impl PreferLayer {
+    /// Create a new layer, which reduces minimal responses to documents without embedded
+    /// entities or actions.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify how to reduce responses for clients that ask for a minimal response.
+    ///
+    /// # Parameters
+    /// - `minimal` - How to reduce the responses.
+    #[must_use]
+    pub fn with_minimal_response(mut self, minimal: MinimalResponse) -> Self {
+        self.minimal = minimal;
+
+        self
+    }
+}
+
+impl<S> Layer<S> for PreferLayer {
+    type Service = PreferService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PreferService {
+            inner,
+            minimal: self.minimal,
+        }
+    }
+}
+
+/// Service produced by [`PreferLayer`].
+#[derive(Debug, Clone)]
+pub struct PreferService<S> {
+    inner:   S,
+    minimal: MinimalResponse,
+}
+
+impl<S, B> Service<Request<B>> for PreferService<S>
+where
+    S: Service<Request<B>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let preference = ReturnPreference::from_headers(request.headers());
+        let minimal = self.minimal;
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+
+            if !is_siren(&response) {
+                return Ok(response);
+            }
+
+            response
+                .headers_mut()
+                .append(VARY, HeaderValue::from_static("Prefer"));
+
+            let preference = match preference {
+                Some(preference) if response.status().is_success() => preference,
+                _ => return Ok(response),
+            };
+
+            let mut response = match (preference, minimal) {
+                (ReturnPreference::Representation, _) => response,
+                (ReturnPreference::Minimal, MinimalResponse::Reduced) => {
+                    map_document(response, Document::into_minimal).await
+                },
+                (ReturnPreference::Minimal, MinimalResponse::NoContent) => {
+                    no_content(response).await
+                },
+            };
+
+            response.headers_mut().insert(
+                PREFERENCE_APPLIED,
+                HeaderValue::from_static(preference.as_str()),
+            );
+
+            Ok(response)
+        })
+    }
+}
+
+/// Replace the given Siren response with a `204 No Content` response that refers to the self link
+/// of the document. If the document does not have a self link then it is reduced instead.
+async fn no_content(response: Response) -> Response {
+    let (mut parts, body) = response.into_parts();
+
+    let bytes = match read_body(body).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let document = match serde_json::from_slice::<Document<Value>>(&bytes) {
+        Ok(document) => document,
+        Err(_) => return Response::from_parts(parts, boxed(Full::from(bytes))),
+    };
+
+    let location = if let Some(location) = document
+        .self_link()
+        .and_then(|link| HeaderValue::from_str(&link.href).ok())
+    {
+        location
+    } else {
+        let response = Response::from_parts(parts, boxed(Full::from(bytes)));
+        return map_document(response, Document::into_minimal).await;
+    };
+
+    parts.status = StatusCode::NO_CONTENT;
+    parts.headers.remove(CONTENT_TYPE);
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.headers.insert(LOCATION, location);
+
+    Response::from_parts(parts, boxed(Empty::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{body::Body, routing::get, Router};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{Action, Link};
+
+    async fn get_order() -> crate::Response<Value> {
+        Document::new(json!({ "orderNumber": 42 }))
+            .with_embedded_link(Link::new("/customers/pj123").with_rel("customer"))
+            .with_action(Action::new("add-item", "/orders/42/items"))
+            .with_link(Link::new("/orders/42").with_rel("self"))
+            .into()
+    }
+
+    async fn send(layer: PreferLayer, prefer: Option<&'static str>) -> Response {
+        let mut request = Request::builder().uri("/orders/42");
+        if let Some(prefer) = prefer {
+            request = request.header("Prefer", prefer);
+        }
+
+        Router::new()
+            .route("/orders/42", get(get_order))
+            .layer(layer)
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body(response: Response) -> Value {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn no_preference() {
+        let response = send(PreferLayer::new(), None).await;
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers().get(VARY).unwrap() == "Prefer");
+        check!(response.headers().get(PREFERENCE_APPLIED) == None);
+
+        let body = body(response).await;
+        check!(body["entities"].as_array().unwrap().len() == 1);
+        check!(body["actions"].as_array().unwrap().len() == 1);
+    }
+
+    #[tokio::test]
+    async fn return_representation() {
+        let response = send(PreferLayer::new(), Some("return=representation")).await;
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers().get(PREFERENCE_APPLIED).unwrap() == "return=representation");
+        check!(body(response).await["actions"].as_array().unwrap().len() == 1);
+    }
+
+    #[tokio::test]
+    async fn return_minimal_reduced() {
+        let response = send(PreferLayer::new(), Some("return=minimal")).await;
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers().get(PREFERENCE_APPLIED).unwrap() == "return=minimal");
+        check!(
+            body(response).await
+                == json!({
+                    "properties": { "orderNumber": 42 },
+                    "links": [{ "rel": ["self"], "href": "/orders/42" }]
+                })
+        );
+    }
+
+    #[tokio::test]
+    async fn return_minimal_no_content() {
+        let layer = PreferLayer::new().with_minimal_response(MinimalResponse::NoContent);
+        let response = send(layer, Some("return=minimal")).await;
+
+        check!(response.status() == StatusCode::NO_CONTENT);
+        check!(response.headers().get(LOCATION).unwrap() == "/orders/42");
+        check!(response.headers().get(CONTENT_TYPE) == None);
+        check!(response.headers().get(PREFERENCE_APPLIED).unwrap() == "return=minimal");
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        check!(body.is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use http::{header::CONTENT_TYPE, HeaderValue, Method, Request};
+use tower::{Layer, Service};
+
+use super::{is_siren, map_document};
+use crate::{AlpsDocument, ResourceProfile};
+
+impl IntoResponse for AlpsDocument {
+    fn into_response(self) -> Response {
+        let mut response = Json(self).into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/alps+json"),
+        );
+
+        response
+    }
+}
+
+/// Layer that serves the ALPS documents for a set of [`ResourceProfile`]s, and links to them from
+/// every Siren document of the types they describe.
+///
+/// - `GET` requests for the href of a profile are answered with its ALPS document, without
+///   calling the wrapped service.
+/// - Successful Siren responses whose document matches a profile, as determined by
+///   [`ResourceProfile::matches`], get a link to that profile with a relation of `profile`.
+///
+/// ```rust,no_run
+/// # use axum::{routing::get, Router};
+/// # use http_siren::{axum::ProfileLayer, ResourceProfile};
+/// # async fn get_order() -> http_siren::Response<()> { todo!() }
+/// let app: Router = Router::new()
+///     .route("/orders/:id", get(get_order))
+///     .layer(ProfileLayer::new().with_profile(
+///         ResourceProfile::new("order", "/profiles/order").with_class("order"),
+///     ));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProfileLayer {
+    profiles: Arc<Vec<ResourceProfile>>,
+}
+
+impl ProfileLayer {
+    /// Create a new layer, with no profiles.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify a profile to serve and link to.
+    ///
+    /// # Parameters
+    /// - `profile` - The profile.
+    #[must_use]
+    pub fn with_profile(mut self, profile: ResourceProfile) -> Self {
+        Arc::make_mut(&mut self.profiles).push(profile);
+
+        self
+    }
+}
+
+impl<S> Layer<S> for ProfileLayer {
+    type Service = ProfileService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ProfileService {
+            inner,
+            profiles: self.profiles.clone(),
+        }
+    }
+}
+
+/// Service produced by [`ProfileLayer`].
+#[derive(Debug, Clone)]
+pub struct ProfileService<S> {
+    inner:    S,
+    profiles: Arc<Vec<ResourceProfile>>,
+}
+
+impl<S, B> Service<Request<B>> for ProfileService<S>
+where
+    S: Service<Request<B>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        if request.method() == Method::GET {
+            let served = self
+                .profiles
+                .iter()
+                .find(|profile| profile.href == request.uri().path());
+
+            if let Some(profile) = served {
+                let response = profile.to_alps().into_response();
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+
+        let profiles = self.profiles.clone();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await?;
+
+            if profiles.is_empty() || !response.status().is_success() || !is_siren(&response) {
+                return Ok(response);
+            }
+
+            Ok(map_document(response, |document| {
+                match profiles.iter().find(|profile| profile.matches(&document)) {
+                    Some(profile) => document.with_profile(profile),
+                    None => document,
+                }
+            })
+            .await)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{body::Body, routing::get, Router};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::Document;
+
+    async fn get_order() -> crate::Response<Value> {
+        Document::new(json!({"orderNumber": 42}))
+            .with_class("order")
+            .into()
+    }
+
+    async fn get_customer() -> crate::Response<Value> {
+        Document::new(json!({"customerId": "pj123"}))
+            .with_class("customer")
+            .into()
+    }
+
+    async fn send(uri: &str) -> Response {
+        Router::new()
+            .route("/orders/42", get(get_order))
+            .route("/customers/pj123", get(get_customer))
+            .layer(ProfileLayer::new().with_profile(
+                ResourceProfile::new("order", "/profiles/order")
+                    .with_class("order")
+                    .with_property("orderNumber"),
+            ))
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body(response: Response) -> Value {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn profile_link() {
+        let body = body(send("/orders/42").await).await;
+
+        check!(body["links"] == json!([{"rel": ["profile"], "href": "/profiles/order"}]));
+    }
+
+    #[tokio::test]
+    async fn unmatched_document() {
+        let body = body(send("/customers/pj123").await).await;
+
+        check!(body.get("links") == None);
+    }
+
+    #[tokio::test]
+    async fn serve_profile() {
+        let response = send("/profiles/order").await;
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/alps+json");
+
+        let body = body(response).await;
+        check!(body["alps"]["descriptor"][0]["id"] == json!("order"));
+    }
+}
@@ -0,0 +1,169 @@
+use std::ops::{Deref, DerefMut};
+
+use axum::{
+    async_trait,
+    extract::{FromRequest, RequestParts},
+};
+use http::StatusCode;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{Action, ErrorDocument};
+
+/// Extractor for the query parameters of a search, as described by
+/// [`Action::from_query`](crate::Action::from_query).
+///
+/// This works the same as Axum's `Query` extractor, except that parameters with an empty value
+/// are treated as if they were not present - which is what a client submitting a form for the
+/// search action will send for any fields that were left blank - and that failures are rendered as
+/// Siren error documents.
+///
+/// ```rust
+/// # use serde::{Deserialize, Serialize};
+/// # use http_siren::axum::SearchQuery;
+/// #[derive(Serialize, Deserialize)]
+/// struct OrderSearch {
+///     status: Option<String>,
+/// }
+///
+/// async fn list_orders(query: SearchQuery<OrderSearch>) -> http_siren::Response<()> {
+///     http_siren::Document::new(())
+///         .with_action(query.action("search", "/orders"))
+///         .into()
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchQuery<T>(pub T);
+
+impl<T> SearchQuery<T>
+where
+    T: Serialize,
+{
+    /// Create a search action for this query, with the current values pre-filled.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the action.
+    /// - `href` - The href for the action.
+    pub fn action<N, H>(&self, name: N, href: H) -> Action
+    where
+        N: ToString,
+        H: ToString,
+    {
+        Action::from_query(name, href, &self.0)
+    }
+}
+
+#[async_trait]
+impl<B, T> FromRequest<B> for SearchQuery<T>
+where
+    B: Send,
+    T: DeserializeOwned,
+{
+    type Rejection = ErrorDocument;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let query = req.uri().query().unwrap_or_default();
+
+        parse_query(query).map(Self).map_err(|e| {
+            ErrorDocument::new(StatusCode::BAD_REQUEST)
+                .with_detail(format!("Failed to deserialize query string: {e}"))
+        })
+    }
+}
+
+/// Parse the given query string, ignoring any parameters with empty values.
+fn parse_query<T>(query: &str) -> Result<T, serde_urlencoded::de::Error>
+where
+    T: DeserializeOwned,
+{
+    let pairs: Vec<(String, String)> = serde_urlencoded::from_str(query)?;
+    let pairs: Vec<_> = pairs.into_iter().filter(|(_, v)| !v.is_empty()).collect();
+
+    // Re-encoding pairs of strings can not fail.
+    let query = serde_urlencoded::to_string(pairs).unwrap_or_default();
+
+    serde_urlencoded::from_str(&query)
+}
+
+impl<T> Deref for SearchQuery<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SearchQuery<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{body::Body, response::IntoResponse, routing::get, Router};
+    use http::Request;
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::Document;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct OrderSearch {
+        status:    Option<String>,
+        page_size: Option<u32>,
+    }
+
+    async fn list_orders(query: SearchQuery<OrderSearch>) -> crate::Response<Value> {
+        Document::new(json!({}))
+            .with_action(query.action("search", "/orders"))
+            .into()
+    }
+
+    async fn send(uri: &str) -> axum::response::Response {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        Router::new()
+            .route("/orders", get(list_orders))
+            .oneshot(request)
+            .await
+            .unwrap()
+            .into_response()
+    }
+
+    #[test]
+    fn parse_empty_values() {
+        let query: OrderSearch = parse_query("status=pending&pageSize=").unwrap();
+
+        check!(query.status == Some("pending".to_owned()));
+        check!(query.page_size == None);
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let response = send("/orders?status=pending&pageSize=20").await;
+        check!(response.status() == StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        check!(
+            body["actions"][0]["fields"]
+                == json!([
+                    { "name": "status", "type": "search", "value": "pending" },
+                    { "name": "pageSize", "type": "number", "value": 20 }
+                ])
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_query() {
+        let response = send("/orders?pageSize=many").await;
+
+        check!(response.status() == StatusCode::BAD_REQUEST);
+        check!(response.headers().get("Content-Type").unwrap() == "application/vnd.siren+json");
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::response::Response;
+use http::Request;
+use tower::{Layer, Service};
+
+use super::{is_siren, map_document};
+use crate::{EmbeddedRepresentation, Link, SparseFieldset};
+
+/// Callback used to resolve embedded links into full representations.
+type Resolver = Arc<dyn Fn(&Link) -> Option<EmbeddedRepresentation> + Send + Sync>;
+
+/// Layer that applies a [`SparseFieldset`] built from the query string of each request to the
+/// Siren documents returned by the wrapped service.
+///
+/// Responses that are not Siren documents, and requests without any of the `fields`, `embed` or
+/// `exclude` query parameters, are passed through untouched.
+#[derive(Clone, Default)]
+pub struct SparseFieldsetLayer {
+    resolver: Option<Resolver>,
+}
+
+impl SparseFieldsetLayer {
+    /// Create a new layer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify a resolver to use for expanding embedded links into full representations when they
+    /// are requested with the `embed` query parameter.
+    ///
+    /// # Parameters
+    /// - `resolver` - The resolver to use.
+    #[must_use]
+    pub fn with_resolver<F>(mut self, resolver: F) -> Self
+    where
+        F: Fn(&Link) -> Option<EmbeddedRepresentation> + Send + Sync + 'static,
+    {
+        self.resolver = Some(Arc::new(resolver));
+
+        self
+    }
+}
+
+impl Debug for SparseFieldsetLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SparseFieldsetLayer")
+            .field("resolver", &self.resolver.is_some())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for SparseFieldsetLayer {
+    type Service = SparseFieldsetService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SparseFieldsetService {
+            inner,
+            resolver: self.resolver.clone(),
+        }
+    }
+}
+
+/// Service produced by [`SparseFieldsetLayer`].
+#[derive(Clone)]
+pub struct SparseFieldsetService<S> {
+    inner:    S,
+    resolver: Option<Resolver>,
+}
+
+impl<S> Debug for SparseFieldsetService<S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SparseFieldsetService")
+            .field("inner", &self.inner)
+            .field("resolver", &self.resolver.is_some())
+            .finish()
+    }
+}
+
+impl<S, B> Service<Request<B>> for SparseFieldsetService<S>
+where
+    S: Service<Request<B>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let fieldset = SparseFieldset::from_query(request.uri().query().unwrap_or_default());
+        let resolver = self.resolver.clone();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await?;
+
+            if fieldset.is_empty() || !is_siren(&response) {
+                return Ok(response);
+            }
+
+            Ok(map_document(response, |document| match resolver {
+                Some(resolver) => fieldset.apply_with_resolver(document, |link| resolver(link)),
+                None => fieldset.apply(document),
+            })
+            .await)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{body::Body, routing::get, Router};
+    use http::header::CONTENT_TYPE;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{values::LinkRelation, Document};
+
+    async fn get_order() -> crate::Response<Value> {
+        Document::new(json!({
+            "orderNumber": 42,
+            "itemCount": 3,
+            "status": "pending"
+        }))
+        .with_embedded_link(Link::new("/customers/pj123").with_rel("customer"))
+        .with_link(Link::new("/orders/42").with_rel(LinkRelation::SelfLink))
+        .into()
+    }
+
+    async fn send(layer: SparseFieldsetLayer, uri: &str) -> Value {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = Router::new()
+            .route("/orders/42", get(get_order))
+            .layer(layer)
+            .oneshot(request)
+            .await
+            .unwrap();
+        check!(response.headers().get(CONTENT_TYPE).unwrap() == "application/vnd.siren+json");
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn no_parameters() {
+        let body = send(SparseFieldsetLayer::new(), "/orders/42").await;
+
+        check!(
+            body["properties"] == json!({"orderNumber": 42, "itemCount": 3, "status": "pending"})
+        );
+    }
+
+    #[tokio::test]
+    async fn sparse_fields() {
+        let body = send(
+            SparseFieldsetLayer::new(),
+            "/orders/42?fields=orderNumber,status&exclude=links",
+        )
+        .await;
+
+        check!(body["properties"] == json!({"orderNumber": 42, "status": "pending"}));
+        check!(body.get("links") == None);
+        check!(body["entities"][0]["href"] == json!("/customers/pj123"));
+    }
+
+    #[tokio::test]
+    async fn embed_with_resolver() {
+        let layer = SparseFieldsetLayer::new().with_resolver(|link| {
+            Some(EmbeddedRepresentation::new(
+                json!({ "customerId": "pj123", "href": link.href }),
+            ))
+        });
+        let body = send(layer, "/orders/42?embed=customer").await;
+
+        check!(
+            body["entities"][0]
+                == json!({
+                    "rel": ["customer"],
+                    "properties": { "customerId": "pj123", "href": "/customers/pj123" }
+                })
+        );
+    }
+}
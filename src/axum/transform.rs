@@ -0,0 +1,276 @@
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::response::Response;
+use http::Request;
+use serde_json::Value;
+use tower::{Layer, Service};
+
+use super::{is_siren, map_document, request_head};
+use crate::{visit::VisitorMut, Document};
+
+/// Callback that applies a single transformer to a document.
+type Transformer = Arc<dyn Fn(&Request<()>, &mut Document<Value>) + Send + Sync>;
+
+/// Layer that runs a pipeline of [`VisitorMut`] transformers over every Siren document returned by
+/// the wrapped service, so that cross-cutting concerns such as rewriting hrefs, filtering out
+/// links and actions, localising titles or marking deprecations are implemented once instead of
+/// in every handler.
+///
+/// Transformers are applied in the order they are added, and only to successful Siren responses.
+///
+/// ```rust,no_run
+/// # use axum::{routing::get, Router};
+/// # use http_siren::{axum::TransformLayer, visit::VisitorMut, Link};
+/// # async fn get_order() -> http_siren::Response<()> { todo!() }
+/// #[derive(Clone)]
+/// struct Rewrite(String);
+///
+/// impl VisitorMut for Rewrite {
+///     fn visit_link_mut(&mut self, link: &mut Link) -> bool {
+///         link.href = format!("{}{}", self.0, link.href);
+///         true
+///     }
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/orders/:id", get(get_order))
+///     .layer(TransformLayer::new().with_transformer(Rewrite("https://api.x.io".to_owned())));
+/// ```
+#[derive(Clone, Default)]
+pub struct TransformLayer {
+    transformers: Vec<Transformer>,
+}
+
+impl TransformLayer {
+    /// Create a new layer, with no transformers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify a transformer to apply to every document. A fresh copy of the transformer is used
+    /// for each document, so any state it gathers is not shared between responses.
+    ///
+    /// # Parameters
+    /// - `transformer` - The transformer.
+    #[must_use]
+    pub fn with_transformer<V>(self, transformer: V) -> Self
+    where
+        V: VisitorMut + Clone + Send + Sync + 'static,
+    {
+        self.with_request_transformer(move |_| transformer.clone())
+    }
+
+    /// Specify a transformer to apply to every document that depends on the request, such as one
+    /// that localises titles according to the `Accept-Language` header.
+    ///
+    /// # Parameters
+    /// - `factory` - Callback given the request, without its body, that builds the transformer.
+    #[must_use]
+    pub fn with_request_transformer<F, V>(mut self, factory: F) -> Self
+    where
+        F: Fn(&Request<()>) -> V + Send + Sync + 'static,
+        V: VisitorMut,
+    {
+        self.transformers.push(Arc::new(move |request, document| {
+            document.accept_mut(&mut factory(request));
+        }));
+
+        self
+    }
+}
+
+impl Debug for TransformLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransformLayer")
+            .field("transformers", &self.transformers.len())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for TransformLayer {
+    type Service = TransformService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TransformService {
+            inner,
+            transformers: Arc::new(self.transformers.clone()),
+        }
+    }
+}
+
+/// Service produced by [`TransformLayer`].
+#[derive(Clone)]
+pub struct TransformService<S> {
+    inner:        S,
+    transformers: Arc<Vec<Transformer>>,
+}
+
+impl<S> Debug for TransformService<S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransformService")
+            .field("inner", &self.inner)
+            .field("transformers", &self.transformers.len())
+            .finish()
+    }
+}
+
+impl<S, B> Service<Request<B>> for TransformService<S>
+where
+    S: Service<Request<B>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let head = request_head(&request);
+        let transformers = self.transformers.clone();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await?;
+
+            if transformers.is_empty() || !response.status().is_success() || !is_siren(&response)
+            {
+                return Ok(response);
+            }
+
+            Ok(map_document(response, |mut document| {
+                for transformer in transformers.iter() {
+                    transformer(&head, &mut document);
+                }
+
+                document
+            })
+            .await)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{body::Body, routing::get, Router};
+    use http::{header::ACCEPT_LANGUAGE, StatusCode};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{Action, ErrorDocument, Link};
+
+    async fn get_order() -> crate::Response<Value> {
+        Document::new(json!({"orderNumber": 42}))
+            .with_link(Link::new("/orders/42").with_rel("self"))
+            .with_action(Action::new("cancel", "/orders/42").with_title("Cancel"))
+            .with_action(Action::new("pay", "/orders/42/payment").with_title("Pay"))
+            .into()
+    }
+
+    async fn missing() -> ErrorDocument {
+        ErrorDocument::new(StatusCode::NOT_FOUND).with_link(Link::new("/orders").with_rel("up"))
+    }
+
+    /// Rewrites hrefs onto another origin.
+    #[derive(Clone)]
+    struct Rewrite;
+
+    impl VisitorMut for Rewrite {
+        fn visit_link_mut(&mut self, link: &mut Link) -> bool {
+            link.href = format!("https://api.x.io{}", link.href);
+            true
+        }
+    }
+
+    /// Marks the `cancel` action as deprecated.
+    #[derive(Clone)]
+    struct Deprecate;
+
+    impl VisitorMut for Deprecate {
+        fn visit_action_mut(&mut self, action: &mut Action) -> bool {
+            if action.name == "cancel" {
+                action.class.push("deprecated".to_owned());
+            }
+            true
+        }
+    }
+
+    /// Translates the titles of actions into the requested language.
+    struct Localise(Option<String>);
+
+    impl VisitorMut for Localise {
+        fn visit_action_mut(&mut self, action: &mut Action) -> bool {
+            if self.0.as_deref() == Some("fr") {
+                action.title = match action.title.as_deref() {
+                    Some("Cancel") => Some("Annuler".to_owned()),
+                    Some("Pay") => Some("Payer".to_owned()),
+                    _ => action.title.take(),
+                };
+            }
+            true
+        }
+    }
+
+    async fn send(uri: &str) -> Value {
+        let response = Router::new()
+            .route("/orders/42", get(get_order))
+            .route("/missing", get(missing))
+            .layer(
+                TransformLayer::new()
+                    .with_transformer(Rewrite)
+                    .with_transformer(Deprecate)
+                    .with_request_transformer(|request| {
+                        Localise(
+                            request
+                                .headers()
+                                .get(ACCEPT_LANGUAGE)
+                                .and_then(|v| v.to_str().ok())
+                                .map(ToOwned::to_owned),
+                        )
+                    }),
+            )
+            .oneshot(
+                Request::get(uri)
+                    .header(ACCEPT_LANGUAGE, "fr")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn transform_document() {
+        let body = send("/orders/42").await;
+
+        check!(body["links"] == json!([{"rel": ["self"], "href": "https://api.x.io/orders/42"}]));
+        check!(body["actions"][0]["class"] == json!(["deprecated"]));
+        check!(body["actions"][0]["title"] == json!("Annuler"));
+        check!(body["actions"][1].get("class") == None);
+        check!(body["actions"][1]["title"] == json!("Payer"));
+    }
+
+    #[tokio::test]
+    async fn skip_errors() {
+        let body = send("/missing").await;
+
+        check!(body["links"] == json!([{"rel": ["up"], "href": "/orders"}]));
+    }
+}
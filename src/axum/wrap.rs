@@ -0,0 +1,365 @@
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::response::{IntoResponse, Response};
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Request,
+    StatusCode,
+};
+use serde_json::Value;
+use tower::{Layer, Service};
+
+use super::{read_body, refresh_representation_headers, request_head};
+use crate::{Document, Link};
+
+/// Callback used to add links, actions and anything else to a wrapped document.
+type Decorator = Arc<dyn Fn(&Request<()>, Document<Value>) -> Document<Value> + Send + Sync>;
+
+/// Layer that turns the plain JSON responses of the wrapped service into Siren documents, so that
+/// existing handlers can be served as Siren without rewriting them to return
+/// [`Response`](crate::Response).
+///
+/// Successful responses with a `Content-Type` of `application/json` and a JSON object as their body
+/// have the object wrapped as the properties of a document, which is then passed through each
+/// decorator in turn before being returned with a `Content-Type` of `application/vnd.siren+json`.
+/// Any other headers of the response are kept, except that an `ETag` is generated again for the
+/// document and a `Siren-Signature` is removed since they described the plain JSON body. All other
+/// responses, including unsuccessful ones and those with arrays or scalars as their body, are
+/// passed through untouched since Siren properties must be an object.
+///
+/// Decorators are given the request, without its body, so that they can build links and actions
+/// from it. Apply the layer with `route_layer` to decorate the documents of a single route.
+///
+/// ```rust,no_run
+/// # use axum::{routing::get, Json, Router};
+/// # use http_siren::{axum::WrapJsonLayer, Action};
+/// # use serde_json::{json, Value};
+/// # async fn get_order() -> Json<Value> { Json(json!({"orderNumber": 42})) }
+/// let app: Router = Router::new().route(
+///     "/orders/:id",
+///     get(get_order).route_layer(
+///         WrapJsonLayer::new()
+///             .with_self_link()
+///             .with_decorator(|request, document| {
+///                 document.with_class("order").with_action(Action::new(
+///                     "add-item",
+///                     format!("{}/items", request.uri().path()),
+///                 ))
+///             }),
+///     ),
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct WrapJsonLayer {
+    decorators: Vec<Decorator>,
+}
+
+impl WrapJsonLayer {
+    /// Create a new layer, with no decorators.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify a decorator to apply to every wrapped document. Decorators are applied in the
+    /// order they are added.
+    ///
+    /// # Parameters
+    /// - `decorator` - Callback given the request and the document, returning the decorated
+    ///   document.
+    #[must_use]
+    pub fn with_decorator<F>(mut self, decorator: F) -> Self
+    where
+        F: Fn(&Request<()>, Document<Value>) -> Document<Value> + Send + Sync + 'static,
+    {
+        self.decorators.push(Arc::new(decorator));
+
+        self
+    }
+
+    /// Add a link with a relation of `self` to every wrapped document, pointing to the URI of the
+    /// request.
+    #[must_use]
+    pub fn with_self_link(self) -> Self {
+        self.with_decorator(|request, document| {
+            document.with_link(Link::new(request.uri()).with_rel("self"))
+        })
+    }
+}
+
+impl Debug for WrapJsonLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WrapJsonLayer")
+            .field("decorators", &self.decorators.len())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for WrapJsonLayer {
+    type Service = WrapJsonService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        WrapJsonService {
+            inner,
+            decorators: Arc::new(self.decorators.clone()),
+        }
+    }
+}
+
+/// Service produced by [`WrapJsonLayer`].
+#[derive(Clone)]
+pub struct WrapJsonService<S> {
+    inner:      S,
+    decorators: Arc<Vec<Decorator>>,
+}
+
+impl<S> Debug for WrapJsonService<S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WrapJsonService")
+            .field("inner", &self.inner)
+            .field("decorators", &self.decorators.len())
+            .finish()
+    }
+}
+
+impl<S, B> Service<Request<B>> for WrapJsonService<S>
+where
+    S: Service<Request<B>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let head = request_head(&request);
+        let decorators = self.decorators.clone();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await?;
+
+            if !response.status().is_success() || !is_json(&response) {
+                return Ok(response);
+            }
+
+            Ok(wrap(response, &head, &decorators).await)
+        })
+    }
+}
+
+/// Check if the given response is plain JSON.
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(false, |v| v.starts_with("application/json"))
+}
+
+/// Wrap the JSON body of the given response into a decorated Siren document. If the body is not a
+/// JSON object then the response is returned unchanged.
+async fn wrap(response: Response, request: &Request<()>, decorators: &[Decorator]) -> Response {
+    let (parts, body) = response.into_parts();
+
+    let bytes = match read_body(body).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    let properties = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(properties @ Value::Object(_)) => properties,
+        _ => {
+            return Response::from_parts(parts, axum::body::boxed(axum::body::Full::from(bytes)));
+        },
+    };
+
+    let document = decorators
+        .iter()
+        .fold(Document::new(properties), |document, decorator| {
+            decorator(request, document)
+        });
+
+    // The entity tag and signature of the original describe the plain JSON body, not the document.
+    let mut original = parts.headers;
+    original.remove(CONTENT_TYPE);
+    original.remove(CONTENT_LENGTH);
+    refresh_representation_headers(&mut original, &document);
+
+    let mut response = crate::Response::from(document)
+        .with_status_code(parts.status)
+        .into_response();
+
+    // Headers that the Siren response already has take precedence over those of the original.
+    let headers = response.headers_mut();
+    let existing: HashSet<_> = headers.keys().cloned().collect();
+    for (name, value) in &original {
+        if !existing.contains(name) {
+            headers.append(name, value.clone());
+        }
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use axum::{
+        body::Body,
+        response::AppendHeaders,
+        routing::{get, post},
+        Json,
+        Router,
+    };
+    use http::header::{HeaderName, ETAG, LOCATION, SET_COOKIE};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::Action;
+
+    async fn get_order() -> Json<Value> {
+        Json(json!({"orderNumber": 42}))
+    }
+
+    async fn create_order() -> impl IntoResponse {
+        (
+            StatusCode::CREATED,
+            AppendHeaders([
+                (LOCATION, "/orders/43"),
+                (SET_COOKIE, "a=1"),
+                (SET_COOKIE, "b=2"),
+            ]),
+            Json(json!({"orderNumber": 43})),
+        )
+    }
+
+    async fn get_customer() -> impl IntoResponse {
+        (
+            [(ETAG, "\"plain\""), (HeaderName::from_static("siren-signature"), "plain")],
+            Json(json!({"customerId": "pj123"})),
+        )
+    }
+
+    async fn missing() -> impl IntoResponse {
+        (StatusCode::NOT_FOUND, Json(json!({"error": "Not found"})))
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .route(
+                "/orders/:id",
+                get(get_order).route_layer(WrapJsonLayer::new().with_self_link().with_decorator(
+                    |request, document| {
+                        document.with_class("order").with_action(Action::new(
+                            "add-item",
+                            format!("{}/items", request.uri().path()),
+                        ))
+                    },
+                )),
+            )
+            .route("/orders", post(create_order))
+            .route("/customers/pj123", get(get_customer))
+            .route("/missing", get(missing))
+            .route("/text", get(|| async { "Hello" }))
+            .route("/tags", get(|| async { Json(json!(["gift", "urgent"])) }))
+            .route("/count", get(|| async { Json(json!(42)) }))
+            .layer(WrapJsonLayer::new())
+    }
+
+    async fn send(request: Request<Body>) -> (Response, Value) {
+        let response = router().oneshot(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await.unwrap();
+
+        (
+            Response::from_parts(parts, axum::body::boxed(axum::body::Full::from(vec![]))),
+            serde_json::from_slice(&body).unwrap_or_else(|_| json!(String::from_utf8_lossy(&body))),
+        )
+    }
+
+    #[tokio::test]
+    async fn wrap_with_decorators() {
+        let request = Request::get("/orders/42?expand=items")
+            .body(Body::empty())
+            .unwrap();
+        let (response, body) = send(request).await;
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers()[CONTENT_TYPE] == "application/vnd.siren+json");
+        check!(
+            body == json!({
+                "class": ["order"],
+                "properties": {"orderNumber": 42},
+                "links": [{"rel": ["self"], "href": "/orders/42?expand=items"}],
+                "actions": [{"name": "add-item", "href": "/orders/42/items"}]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn keep_status_and_headers() {
+        let (response, body) = send(Request::post("/orders").body(Body::empty()).unwrap()).await;
+
+        check!(response.status() == StatusCode::CREATED);
+        check!(response.headers()[LOCATION] == "/orders/43");
+        check!(response.headers().get_all(SET_COOKIE).iter().count() == 2);
+        check!(response.headers().get_all(CONTENT_TYPE).iter().count() == 1);
+        check!(response.headers()[CONTENT_TYPE] == "application/vnd.siren+json");
+        check!(body == json!({"properties": {"orderNumber": 43}}));
+    }
+
+    #[tokio::test]
+    async fn refresh_etag_and_signature() {
+        let request = Request::get("/customers/pj123").body(Body::empty()).unwrap();
+        let (response, body) = send(request).await;
+
+        let etag = Document::new(json!({"customerId": "pj123"})).etag();
+        check!(body == json!({"properties": {"customerId": "pj123"}}));
+        check!(response.headers().get_all(ETAG).iter().count() == 1);
+        check!(response.headers()[ETAG] == etag.as_str());
+        check!(response.headers().get("Siren-Signature") == None);
+    }
+
+    #[tokio::test]
+    async fn pass_through() {
+        let (response, body) = send(Request::get("/missing").body(Body::empty()).unwrap()).await;
+
+        check!(response.status() == StatusCode::NOT_FOUND);
+        check!(response.headers()[CONTENT_TYPE] == "application/json");
+        check!(body == json!({"error": "Not found"}));
+
+        let (response, body) = send(Request::get("/text").body(Body::empty()).unwrap()).await;
+
+        check!(response.headers()[CONTENT_TYPE] == "text/plain; charset=utf-8");
+        check!(body == json!("Hello"));
+    }
+
+    #[tokio::test]
+    async fn pass_through_non_objects() {
+        let (response, body) = send(Request::get("/tags").body(Body::empty()).unwrap()).await;
+
+        check!(response.headers()[CONTENT_TYPE] == "application/json");
+        check!(body == json!(["gift", "urgent"]));
+
+        let (response, body) = send(Request::get("/count").body(Body::empty()).unwrap()).await;
+
+        check!(response.headers()[CONTENT_TYPE] == "application/json");
+        check!(body == json!(42));
+    }
+}
@@ -0,0 +1,213 @@
+use http_siren::{Action, Document, Entity, Link};
+use serde_json::{json, Map, Value};
+
+/// Convert a document into HAL.
+///
+/// - Properties become the members of the resource.
+/// - Links and embedded links become `_links`, keyed by each of their link relations.
+/// - Embedded representations become `_embedded` resources, keyed by each of their link relations.
+/// - Actions become HAL-FORMS `_templates`, keyed by their names.
+///
+/// Classes have no equivalent in HAL, so are dropped.
+pub fn convert(document: &Document<Value>) -> Value {
+    resource(
+        &document.properties,
+        &document.entities,
+        &document.links,
+        &document.actions,
+    )
+}
+
+/// Build a HAL resource from the contents of an entity.
+fn resource(properties: &Value, entities: &[Entity], links: &[Link], actions: &[Action]) -> Value {
+    let mut result = match properties {
+        Value::Object(members) => members.clone(),
+        Value::Null => Map::new(),
+        value => Map::from_iter([("properties".to_owned(), value.clone())]),
+    };
+
+    let mut hal_links = Grouped::default();
+    let mut embedded = Grouped::default();
+
+    for link in links {
+        hal_links.insert(&link.rel, link_object(link));
+    }
+
+    for entity in entities {
+        match entity {
+            Entity::Link(link) => hal_links.insert(&link.rel, link_object(link)),
+            Entity::Representation(representation) => embedded.insert(
+                &representation.rel,
+                resource(
+                    &representation.properties,
+                    &representation.entities,
+                    &representation.links,
+                    &representation.actions,
+                ),
+            ),
+        }
+    }
+
+    if !hal_links.is_empty() {
+        result.insert("_links".to_owned(), hal_links.into_value());
+    }
+    if !embedded.is_empty() {
+        result.insert("_embedded".to_owned(), embedded.into_value());
+    }
+    if !actions.is_empty() {
+        let templates = actions
+            .iter()
+            .map(|action| (action.name.clone(), template(action)))
+            .collect::<Map<_, _>>();
+        result.insert("_templates".to_owned(), Value::Object(templates));
+    }
+
+    Value::Object(result)
+}
+
+/// Build a HAL link object.
+fn link_object(link: &Link) -> Value {
+    let mut result = Map::from_iter([("href".to_owned(), json!(link.href))]);
+    if let Some(title) = &link.title {
+        result.insert("title".to_owned(), json!(title));
+    }
+    if let Some(media_type) = &link.media_type {
+        result.insert("type".to_owned(), json!(media_type));
+    }
+
+    Value::Object(result)
+}
+
+/// Build a HAL-FORMS template for an action.
+fn template(action: &Action) -> Value {
+    let mut result = Map::from_iter([
+        ("method".to_owned(), json!(action.method().as_str())),
+        ("target".to_owned(), json!(action.href)),
+    ]);
+    if let Some(title) = &action.title {
+        result.insert("title".to_owned(), json!(title));
+    }
+    if let Some(media_type) = &action.media_type {
+        result.insert("contentType".to_owned(), json!(media_type));
+    }
+
+    let properties = action
+        .fields
+        .iter()
+        .map(|field| {
+            let mut property = Map::from_iter([("name".to_owned(), json!(field.name))]);
+            if let Some(input_type) = &field.input_type {
+                property.insert("type".to_owned(), json!(input_type));
+            }
+            if let Some(title) = &field.title {
+                property.insert("prompt".to_owned(), json!(title));
+            }
+            if let Some(value) = field.value.as_ref().filter(|_| field.options().is_empty()) {
+                property.insert("value".to_owned(), value.clone());
+            }
+            if field.required {
+                property.insert("required".to_owned(), json!(true));
+            }
+
+            Value::Object(property)
+        })
+        .collect::<Vec<_>>();
+    result.insert("properties".to_owned(), Value::Array(properties));
+
+    Value::Object(result)
+}
+
+/// Values grouped by link relation, rendered as a single value for relations that have only one
+/// and as an array for those that have several.
+#[derive(Default)]
+struct Grouped(Map<String, Value>);
+
+impl Grouped {
+    fn insert(&mut self, rels: &[String], value: Value) {
+        for rel in rels {
+            match self.0.get_mut(rel) {
+                None => {
+                    self.0.insert(rel.clone(), value.clone());
+                },
+                Some(Value::Array(values)) => values.push(value.clone()),
+                Some(existing) => *existing = json!([existing.take(), value.clone()]),
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn into_value(self) -> Value {
+        Value::Object(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+
+    #[test]
+    fn convert_example() {
+        let document = serde_json::from_value(crate::example()).unwrap();
+
+        check!(
+            convert(&document)
+                == json!({
+                    "orderNumber": 42,
+                    "itemCount": 3,
+                    "status": "pending",
+                    "_links": {
+                        "self": { "href": "http://api.x.io/orders/42" },
+                        "previous": { "href": "http://api.x.io/orders/41" },
+                        "next": { "href": "http://api.x.io/orders/43" },
+                        "http://x.io/rels/order-items": {
+                            "href": "http://api.x.io/orders/42/items"
+                        }
+                    },
+                    "_embedded": {
+                        "http://x.io/rels/customer": {
+                            "customerId": "pj123",
+                            "name": "Peter Joseph",
+                            "_links": {
+                                "self": { "href": "http://api.x.io/customers/pj123" }
+                            }
+                        }
+                    },
+                    "_templates": {
+                        "add-item": {
+                            "method": "POST",
+                            "target": "http://api.x.io/orders/42/items",
+                            "title": "Add Item",
+                            "contentType": "application/x-www-form-urlencoded",
+                            "properties": [
+                                { "name": "orderNumber", "type": "hidden", "value": "42" },
+                                { "name": "productCode", "type": "text" },
+                                { "name": "quantity", "type": "number" }
+                            ]
+                        }
+                    }
+                })
+        );
+    }
+
+    #[test]
+    fn repeated_rels() {
+        let document = Document::new(json!({}))
+            .with_link(Link::new("/items/1").with_rel("item"))
+            .with_link(Link::new("/items/2").with_rel("item"))
+            .with_link(Link::new("/items/3").with_rel("item"));
+
+        check!(
+            convert(&document)["_links"]["item"]
+                == json!([
+                    { "href": "/items/1" },
+                    { "href": "/items/2" },
+                    { "href": "/items/3" }
+                ])
+        );
+    }
+}
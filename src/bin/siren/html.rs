@@ -0,0 +1,309 @@
+use std::fmt::Write;
+
+use http::Method;
+use http_siren::{Action, Document, Entity, Field, Link};
+use serde_json::Value;
+
+/// Convert a document into a standalone HTML page.
+///
+/// - Properties become a definition list.
+/// - Embedded representations become nested articles, and embedded links become anchors.
+/// - Links become anchors, with the link relations in the `rel` attribute.
+/// - Actions become forms, with an input for each field. Forms can only be submitted with `GET` or
+///   `POST`, so other methods are submitted as `POST` with the real method in a `data-method`
+///   attribute.
+pub fn convert(document: &Document<Value>) -> String {
+    let title = document
+        .title
+        .clone()
+        .or_else(|| document.class.first().cloned())
+        .unwrap_or_else(|| "Siren document".to_owned());
+
+    let mut output = String::new();
+    let _ = writeln!(output, "<!DOCTYPE html>");
+    let _ = writeln!(output, "<html>");
+    let _ = writeln!(output, "<head>");
+    let _ = writeln!(output, "<meta charset=\"utf-8\">");
+    let _ = writeln!(output, "<title>{}</title>", escape(&title));
+    let _ = writeln!(output, "</head>");
+    let _ = writeln!(output, "<body>");
+    entity(
+        &mut output,
+        1,
+        &document.class,
+        &[],
+        document.title.as_deref(),
+        &document.properties,
+        &document.entities,
+        &document.links,
+        &document.actions,
+    );
+    let _ = writeln!(output, "</body>");
+    let _ = write!(output, "</html>");
+
+    output
+}
+
+/// Render an entity as an article, with a heading of the given level.
+#[allow(clippy::too_many_arguments)]
+fn entity(
+    output: &mut String,
+    level: usize,
+    class: &[String],
+    rel: &[String],
+    title: Option<&str>,
+    properties: &Value,
+    entities: &[Entity],
+    links: &[Link],
+    actions: &[Action],
+) {
+    let heading = level.min(6);
+    let sub_heading = (level + 1).min(6);
+
+    let _ = write!(output, "<article class=\"{}\"", escape(&class.join(" ")));
+    if !rel.is_empty() {
+        let _ = write!(output, " data-rel=\"{}\"", escape(&rel.join(" ")));
+    }
+    let _ = writeln!(output, ">");
+
+    if let Some(title) = title {
+        let _ = writeln!(output, "<h{heading}>{}</h{heading}>", escape(title));
+    }
+
+    if let Value::Object(members) = properties {
+        if !members.is_empty() {
+            let _ = writeln!(output, "<dl class=\"properties\">");
+            for (name, value) in members {
+                let value = match value {
+                    Value::String(text) => text.clone(),
+                    value => value.to_string(),
+                };
+                let _ = writeln!(output, "<dt>{}</dt><dd>{}</dd>", escape(name), escape(&value));
+            }
+            let _ = writeln!(output, "</dl>");
+        }
+    }
+
+    if !entities.is_empty() {
+        let _ = writeln!(output, "<section class=\"entities\">");
+        let _ = writeln!(output, "<h{sub_heading}>Entities</h{sub_heading}>");
+        for embedded in entities {
+            match embedded {
+                Entity::Link(link) => {
+                    let _ = writeln!(output, "<p>{}</p>", anchor(link));
+                },
+                Entity::Representation(representation) => entity(
+                    output,
+                    level + 2,
+                    &representation.class,
+                    &representation.rel,
+                    representation.title.as_deref(),
+                    &representation.properties,
+                    &representation.entities,
+                    &representation.links,
+                    &representation.actions,
+                ),
+            }
+        }
+        let _ = writeln!(output, "</section>");
+    }
+
+    if !links.is_empty() {
+        let _ = writeln!(output, "<nav class=\"links\">");
+        let _ = writeln!(output, "<h{sub_heading}>Links</h{sub_heading}>");
+        let _ = writeln!(output, "<ul>");
+        for link in links {
+            let _ = writeln!(output, "<li>{}</li>", anchor(link));
+        }
+        let _ = writeln!(output, "</ul>");
+        let _ = writeln!(output, "</nav>");
+    }
+
+    if !actions.is_empty() {
+        let _ = writeln!(output, "<section class=\"actions\">");
+        let _ = writeln!(output, "<h{sub_heading}>Actions</h{sub_heading}>");
+        for action in actions {
+            form(output, action);
+        }
+        let _ = writeln!(output, "</section>");
+    }
+
+    let _ = writeln!(output, "</article>");
+}
+
+/// Render a link as an anchor.
+fn anchor(link: &Link) -> String {
+    let mut result = format!(
+        "<a rel=\"{}\" href=\"{}\"",
+        escape(&link.rel.join(" ")),
+        escape(&link.href)
+    );
+    if !link.class.is_empty() {
+        let _ = write!(result, " class=\"{}\"", escape(&link.class.join(" ")));
+    }
+    if let Some(media_type) = &link.media_type {
+        let _ = write!(result, " type=\"{}\"", escape(media_type));
+    }
+    let text = link.title.as_deref().unwrap_or(&link.href);
+    let _ = write!(result, ">{}</a>", escape(text));
+
+    result
+}
+
+/// Render an action as a form.
+fn form(output: &mut String, action: &Action) {
+    let method = action.method();
+    let form_method = if method == Method::GET { "get" } else { "post" };
+
+    let _ = write!(
+        output,
+        "<form name=\"{}\" action=\"{}\" method=\"{form_method}\"",
+        escape(&action.name),
+        escape(&action.href)
+    );
+    if method != Method::GET && method != Method::POST {
+        let _ = write!(output, " data-method=\"{method}\"");
+    }
+    if !action.class.is_empty() {
+        let _ = write!(output, " class=\"{}\"", escape(&action.class.join(" ")));
+    }
+    if let Some(media_type) = &action.media_type {
+        let _ = write!(output, " enctype=\"{}\"", escape(media_type));
+    }
+    let _ = writeln!(output, ">");
+
+    let label = action.title.as_deref().unwrap_or(&action.name);
+    let _ = writeln!(output, "<fieldset>");
+    let _ = writeln!(output, "<legend>{}</legend>", escape(label));
+    for field in &action.fields {
+        input(output, field);
+    }
+    let _ = writeln!(output, "<button type=\"submit\">{}</button>", escape(label));
+    let _ = writeln!(output, "</fieldset>");
+    let _ = writeln!(output, "</form>");
+}
+
+/// Render a field as a labelled input, or as a group of inputs if it has options.
+fn input(output: &mut String, field: &Field) {
+    let input_type = field.input_type.as_deref().unwrap_or("text");
+    let label = field.title.as_deref().unwrap_or(&field.name);
+    let options = field.options();
+
+    if !options.is_empty() {
+        let _ = writeln!(output, "<fieldset>");
+        let _ = writeln!(output, "<legend>{}</legend>", escape(label));
+        for option in options {
+            let value = match &option.value {
+                Value::String(text) => text.clone(),
+                value => value.to_string(),
+            };
+            let checked = if option.selected { " checked" } else { "" };
+            let _ = writeln!(
+                output,
+                "<label><input type=\"{}\" name=\"{}\" value=\"{}\"{checked}> {}</label>",
+                escape(input_type),
+                escape(&field.name),
+                escape(&value),
+                escape(option.title.as_deref().unwrap_or(&value))
+            );
+        }
+        let _ = writeln!(output, "</fieldset>");
+        return;
+    }
+
+    let mut attributes = format!(
+        "type=\"{}\" name=\"{}\"",
+        escape(input_type),
+        escape(&field.name)
+    );
+    if let Some(value) = &field.value {
+        let value = match value {
+            Value::String(text) => text.clone(),
+            value => value.to_string(),
+        };
+        let _ = write!(attributes, " value=\"{}\"", escape(&value));
+    }
+    if field.required {
+        attributes.push_str(" required");
+    }
+
+    if input_type == "hidden" {
+        let _ = writeln!(output, "<input {attributes}>");
+    } else {
+        let _ = writeln!(output, "<label>{} <input {attributes}></label>", escape(label));
+    }
+}
+
+/// Escape text for use within HTML content or a quoted attribute.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http_siren::FieldOption;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn convert_example() {
+        let document = serde_json::from_value(crate::example()).unwrap();
+        let html = convert(&document);
+
+        check!(html.starts_with("<!DOCTYPE html>\n<html>\n<head>\n"));
+        check!(html.contains("<title>order</title>"));
+        check!(html.contains("<dt>status</dt><dd>pending</dd>"));
+        check!(html.contains(
+            "<p><a rel=\"http://x.io/rels/order-items\" href=\"http://api.x.io/orders/42/items\" \
+             class=\"items collection\">http://api.x.io/orders/42/items</a></p>"
+        ));
+        check!(html.contains(
+            "<article class=\"info customer\" data-rel=\"http://x.io/rels/customer\">"
+        ));
+        check!(html.contains(
+            "<li><a rel=\"next\" href=\"http://api.x.io/orders/43\">\
+             http://api.x.io/orders/43</a></li>"
+        ));
+        check!(html.contains(
+            "<form name=\"add-item\" action=\"http://api.x.io/orders/42/items\" method=\"post\" \
+             enctype=\"application/x-www-form-urlencoded\">"
+        ));
+        check!(html.contains("<input type=\"hidden\" name=\"orderNumber\" value=\"42\">"));
+        check!(html.contains("<label>quantity <input type=\"number\" name=\"quantity\"></label>"));
+        check!(html.ends_with("</body>\n</html>"));
+    }
+
+    #[test]
+    fn other_methods_and_options() {
+        let document = Document::new(json!({ "note": "<b>Fish & Chips</b>" })).with_action(
+            Action::new("update", "/orders/42")
+                .with_method(Method::PUT)
+                .with_field(
+                    Field::new("status")
+                        .with_type("radio")
+                        .with_option(FieldOption::new("paid").with_title("Paid").with_selected()),
+                ),
+        );
+        let html = convert(&document);
+
+        check!(html.contains("<dd>&lt;b&gt;Fish &amp; Chips&lt;/b&gt;</dd>"));
+        check!(html.contains("method=\"post\" data-method=\"PUT\">"));
+        check!(html.contains(
+            "<label><input type=\"radio\" name=\"status\" value=\"paid\" checked> Paid</label>"
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+use http_siren::{Document, Entity, Link};
+use serde_json::{json, Map, Value};
+
+/// Convert a document into JSON:API.
+///
+/// - The document becomes the primary resource, with a type of its first class and an ID taken
+///   from its `id` property, or from its self link if it has no such property.
+/// - The remaining properties become the attributes of the resource.
+/// - Links become the links of the resource, keyed by their first link relation.
+/// - Embedded links become relationships with a `related` link, and embedded representations
+///   become relationships to resources that are included in the document.
+///
+/// Actions have no equivalent in JSON:API, so are dropped.
+pub fn convert(document: &Document<Value>) -> Value {
+    let mut included = vec![];
+    let data = resource(
+        &document.class,
+        &document.properties,
+        &document.entities,
+        &document.links,
+        &mut included,
+    );
+
+    let mut result = Map::from_iter([("data".to_owned(), data)]);
+    if !included.is_empty() {
+        result.insert("included".to_owned(), Value::Array(included));
+    }
+
+    Value::Object(result)
+}
+
+/// Build a JSON:API resource object from the contents of an entity, adding any embedded
+/// representations to the included resources.
+fn resource(
+    class: &[String],
+    properties: &Value,
+    entities: &[Entity],
+    links: &[Link],
+    included: &mut Vec<Value>,
+) -> Value {
+    let mut attributes = match properties {
+        Value::Object(members) => members.clone(),
+        Value::Null => Map::new(),
+        value => Map::from_iter([("properties".to_owned(), value.clone())]),
+    };
+
+    let mut result = identifier(class, attributes.remove("id"), links);
+    if !attributes.is_empty() {
+        result.insert("attributes".to_owned(), Value::Object(attributes));
+    }
+
+    let mut resource_links = Map::new();
+    for link in links {
+        if let Some(rel) = link.rel.first() {
+            if !resource_links.contains_key(rel) {
+                resource_links.insert(rel.clone(), link_object(link));
+            }
+        }
+    }
+    if !resource_links.is_empty() {
+        result.insert("links".to_owned(), Value::Object(resource_links));
+    }
+
+    let mut relationships = Map::new();
+    for entity in entities {
+        let (rel, relationship) = match entity {
+            Entity::Link(link) => (&link.rel, json!({ "links": { "related": link_object(link) } })),
+            Entity::Representation(representation) => {
+                let related = resource(
+                    &representation.class,
+                    &representation.properties,
+                    &representation.entities,
+                    &representation.links,
+                    included,
+                );
+                let identifier = json!({ "type": related["type"], "id": related["id"] });
+                included.push(related);
+
+                (&representation.rel, json!({ "data": identifier }))
+            },
+        };
+
+        let rel = match rel.first() {
+            Some(rel) => rel,
+            None => continue,
+        };
+        match relationships.get_mut(rel) {
+            None => {
+                relationships.insert(rel.clone(), relationship);
+            },
+            Some(existing) => {
+                let identifier = match relationship.get("data") {
+                    Some(identifier) => identifier,
+                    None => continue,
+                };
+
+                match existing.get_mut("data") {
+                    Some(Value::Array(data)) => data.push(identifier.clone()),
+                    Some(data) => *data = json!([data.take(), identifier]),
+                    None => existing["data"] = identifier.clone(),
+                }
+            },
+        }
+    }
+    if !relationships.is_empty() {
+        result.insert("relationships".to_owned(), Value::Object(relationships));
+    }
+
+    Value::Object(result)
+}
+
+/// Build the type and ID of a resource.
+fn identifier(class: &[String], id: Option<Value>, links: &[Link]) -> Map<String, Value> {
+    let resource_type = class.first().map_or("resource", String::as_str);
+    let id = match id {
+        Some(Value::String(id)) => Some(id),
+        Some(id) => Some(id.to_string()),
+        None => links
+            .iter()
+            .find(|link| link.rel.iter().any(|rel| rel == "self"))
+            .map(|link| link.href.clone()),
+    };
+
+    let mut result = Map::from_iter([("type".to_owned(), json!(resource_type))]);
+    if let Some(id) = id {
+        result.insert("id".to_owned(), json!(id));
+    }
+
+    result
+}
+
+/// Build a JSON:API link, which is just the URL unless the link has a title or media type.
+fn link_object(link: &Link) -> Value {
+    if link.title.is_none() && link.media_type.is_none() {
+        return json!(link.href);
+    }
+
+    let mut result = Map::from_iter([("href".to_owned(), json!(link.href))]);
+    if let Some(title) = &link.title {
+        result.insert("title".to_owned(), json!(title));
+    }
+    if let Some(media_type) = &link.media_type {
+        result.insert("type".to_owned(), json!(media_type));
+    }
+
+    Value::Object(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http_siren::EmbeddedRepresentation;
+
+    use super::*;
+
+    #[test]
+    fn convert_example() {
+        let document = serde_json::from_value(crate::example()).unwrap();
+
+        check!(
+            convert(&document)
+                == json!({
+                    "data": {
+                        "type": "order",
+                        "id": "http://api.x.io/orders/42",
+                        "attributes": {
+                            "orderNumber": 42,
+                            "itemCount": 3,
+                            "status": "pending"
+                        },
+                        "links": {
+                            "self": "http://api.x.io/orders/42",
+                            "previous": "http://api.x.io/orders/41",
+                            "next": "http://api.x.io/orders/43"
+                        },
+                        "relationships": {
+                            "http://x.io/rels/order-items": {
+                                "links": { "related": "http://api.x.io/orders/42/items" }
+                            },
+                            "http://x.io/rels/customer": {
+                                "data": { "type": "info", "id": "http://api.x.io/customers/pj123" }
+                            }
+                        }
+                    },
+                    "included": [{
+                        "type": "info",
+                        "id": "http://api.x.io/customers/pj123",
+                        "attributes": { "customerId": "pj123", "name": "Peter Joseph" },
+                        "links": { "self": "http://api.x.io/customers/pj123" }
+                    }]
+                })
+        );
+    }
+
+    #[test]
+    fn to_many_relationships() {
+        let document = Document::new(json!({ "id": 42 }))
+            .with_class("order")
+            .with_embedded_representation(
+                EmbeddedRepresentation::new(json!({ "id": 1 }))
+                    .with_rel("item")
+                    .with_class("item"),
+            )
+            .with_embedded_representation(
+                EmbeddedRepresentation::new(json!({ "id": 2 }))
+                    .with_rel("item")
+                    .with_class("item"),
+            );
+
+        let converted = convert(&document);
+
+        check!(converted["data"]["id"] == json!("42"));
+        check!(converted["data"].get("attributes") == None);
+        check!(
+            converted["data"]["relationships"]["item"]["data"]
+                == json!([{ "type": "item", "id": "1" }, { "type": "item", "id": "2" }])
+        );
+        check!(converted["included"].as_array().unwrap().len() == 2);
+    }
+}
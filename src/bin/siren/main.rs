@@ -0,0 +1,185 @@
+//! Command line tool for inspecting captured Siren documents: validating them against the rules
+//! of the Siren specification, printing an outline of them, and converting them into other
+//! hypermedia formats.
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use http_siren::Document;
+use serde_json::Value;
+
+mod hal;
+mod html;
+mod json_api;
+mod tree;
+mod validate;
+
+/// Validate, inspect and convert Siren documents.
+#[derive(Debug, Parser)]
+#[command(name = "siren", version)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Check that a document follows the rules of the Siren specification.
+    Validate {
+        /// The file to read the document from. Reads from standard input if omitted or `-`.
+        file: Option<PathBuf>,
+    },
+    /// Print an outline of the classes, properties, entities, links and actions of a document.
+    Tree {
+        /// The file to read the document from. Reads from standard input if omitted or `-`.
+        file: Option<PathBuf>,
+    },
+    /// Convert a document into another hypermedia format.
+    Convert {
+        /// The format to convert the document into.
+        #[arg(long, short, value_enum)]
+        to:   Format,
+        /// The file to read the document from. Reads from standard input if omitted or `-`.
+        file: Option<PathBuf>,
+    },
+}
+
+/// The formats that documents can be converted into.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// HAL, with actions as HAL-FORMS templates.
+    Hal,
+    /// JSON:API, with embedded representations as included resources.
+    JsonApi,
+    /// A standalone HTML page, with actions as forms.
+    Html,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let code = match run(args.command) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("siren: {e}");
+            2
+        },
+    };
+
+    process::exit(code);
+}
+
+/// Run the given command, returning the exit code for the process.
+fn run(command: Command) -> Result<i32, String> {
+    match command {
+        Command::Validate { file } => {
+            let errors = validate::validate(&read(file)?);
+            if errors.is_empty() {
+                println!("Document is valid");
+                return Ok(0);
+            }
+
+            for error in &errors {
+                println!("{error}");
+            }
+            Ok(1)
+        },
+        Command::Tree { file } => {
+            print!("{}", tree::render(&parse(read(file)?)?));
+            Ok(0)
+        },
+        Command::Convert { to, file } => {
+            let document = parse(read(file)?)?;
+            let output = match to {
+                Format::Hal => to_json(&hal::convert(&document))?,
+                Format::JsonApi => to_json(&json_api::convert(&document))?,
+                Format::Html => html::convert(&document),
+            };
+
+            println!("{output}");
+            Ok(0)
+        },
+    }
+}
+
+/// Read a JSON value from the given file, or from standard input if there is none.
+fn read(file: Option<PathBuf>) -> Result<Value, String> {
+    let input = match file.filter(|f| f.as_os_str() != "-") {
+        Some(path) => {
+            fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?
+        },
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("stdin: {e}"))?;
+            input
+        },
+    };
+
+    serde_json::from_str(&input).map_err(|e| format!("Invalid JSON: {e}"))
+}
+
+/// Parse a JSON value as a Siren document.
+fn parse(value: Value) -> Result<Document<Value>, String> {
+    serde_json::from_value(value)
+        .map_err(|e| format!("Invalid Siren document: {e}. Use `siren validate` for details"))
+}
+
+/// Serialize a converted document as pretty-printed JSON.
+fn to_json(value: &Value) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+/// The example order document from the Siren specification, used in tests.
+#[cfg(test)]
+fn example() -> Value {
+    serde_json::json!({
+        "class": ["order"],
+        "properties": {
+            "orderNumber": 42,
+            "itemCount": 3,
+            "status": "pending"
+        },
+        "entities": [
+            {
+                "class": ["items", "collection"],
+                "rel": ["http://x.io/rels/order-items"],
+                "href": "http://api.x.io/orders/42/items"
+            },
+            {
+                "class": ["info", "customer"],
+                "rel": ["http://x.io/rels/customer"],
+                "properties": {
+                    "customerId": "pj123",
+                    "name": "Peter Joseph"
+                },
+                "links": [{ "rel": ["self"], "href": "http://api.x.io/customers/pj123" }]
+            }
+        ],
+        "actions": [
+            {
+                "name": "add-item",
+                "title": "Add Item",
+                "method": "POST",
+                "href": "http://api.x.io/orders/42/items",
+                "type": "application/x-www-form-urlencoded",
+                "fields": [
+                    { "name": "orderNumber", "type": "hidden", "value": "42" },
+                    { "name": "productCode", "type": "text" },
+                    { "name": "quantity", "type": "number" }
+                ]
+            }
+        ],
+        "links": [
+            { "rel": ["self"], "href": "http://api.x.io/orders/42" },
+            { "rel": ["previous"], "href": "http://api.x.io/orders/41" },
+            { "rel": ["next"], "href": "http://api.x.io/orders/43" }
+        ]
+    })
+}
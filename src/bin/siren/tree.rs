@@ -0,0 +1,221 @@
+use std::fmt::Write;
+
+use http_siren::{Action, Document, EmbeddedRepresentation, Entity, Link};
+use serde_json::Value;
+
+/// A single node in the outline of a document.
+struct Node {
+    label:    String,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new<S>(label: S, children: Vec<Node>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            label: label.into(),
+            children,
+        }
+    }
+
+    /// Render this node and its children, drawing the branches of the tree.
+    fn render(&self, prefix: &str, output: &mut String) {
+        for (index, child) in self.children.iter().enumerate() {
+            let last = index == self.children.len() - 1;
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            let _ = writeln!(output, "{prefix}{branch}{}", child.label);
+            child.render(&format!("{prefix}{indent}"), output);
+        }
+    }
+}
+
+/// Render an outline of the given document, showing its classes, properties, embedded entities,
+/// links and actions.
+pub fn render(document: &Document<Value>) -> String {
+    let mut label = if document.class.is_empty() {
+        "(no class)".to_owned()
+    } else {
+        document.class.join(", ")
+    };
+    if let Some(title) = &document.title {
+        let _ = write!(label, " \"{title}\"");
+    }
+
+    let root = Node::new(
+        label,
+        entity_nodes(
+            &document.properties,
+            &document.entities,
+            &document.links,
+            &document.actions,
+        ),
+    );
+
+    let mut output = format!("{}\n", root.label);
+    root.render("", &mut output);
+
+    output
+}
+
+/// Build the nodes for the contents of an entity.
+fn entity_nodes(
+    properties: &Value,
+    entities: &[Entity],
+    links: &[Link],
+    actions: &[Action],
+) -> Vec<Node> {
+    let mut nodes = vec![];
+
+    match properties {
+        Value::Object(members) if !members.is_empty() => {
+            let children = members
+                .iter()
+                .map(|(name, value)| Node::new(format!("{name}: {value}"), vec![]))
+                .collect();
+            nodes.push(Node::new("properties", children));
+        },
+        Value::Object(_) | Value::Null => {},
+        value => nodes.push(Node::new(format!("properties: {value}"), vec![])),
+    }
+
+    if !entities.is_empty() {
+        nodes.push(Node::new(
+            "entities",
+            entities.iter().map(embedded_node).collect(),
+        ));
+    }
+
+    if !links.is_empty() {
+        nodes.push(Node::new("links", links.iter().map(link_node).collect()));
+    }
+
+    if !actions.is_empty() {
+        nodes.push(Node::new(
+            "actions",
+            actions.iter().map(action_node).collect(),
+        ));
+    }
+
+    nodes
+}
+
+/// Build the node for an embedded entity.
+fn embedded_node(entity: &Entity) -> Node {
+    match entity {
+        Entity::Link(link) => link_node(link),
+        Entity::Representation(EmbeddedRepresentation {
+            rel,
+            class,
+            properties,
+            entities,
+            links,
+            actions,
+            title,
+            ..
+        }) => {
+            let label = describe(&rel.join(", "), class, title.as_deref());
+
+            Node::new(label, entity_nodes(properties, entities, links, actions))
+        },
+    }
+}
+
+/// Build the node for a link or an embedded link.
+fn link_node(link: &Link) -> Node {
+    let mut label = format!("{} → {}", link.rel.join(", "), link.href);
+    if let Some(media_type) = &link.media_type {
+        let _ = write!(label, " ({media_type})");
+    }
+
+    Node::new(describe(&label, &link.class, link.title.as_deref()), vec![])
+}
+
+/// Build the node for an action and its fields.
+fn action_node(action: &Action) -> Node {
+    let mut label = format!("{}: {} {}", action.name, action.method(), action.href);
+    if let Some(media_type) = &action.media_type {
+        let _ = write!(label, " ({media_type})");
+    }
+
+    let fields = action
+        .fields
+        .iter()
+        .map(|field| {
+            let mut label = field.name.clone();
+            if let Some(input_type) = &field.input_type {
+                let _ = write!(label, " ({input_type})");
+            }
+            if let Some(value) = &field.value {
+                let _ = write!(label, " = {value}");
+            }
+
+            Node::new(describe(&label, &field.class, field.title.as_deref()), vec![])
+        })
+        .collect();
+
+    Node::new(
+        describe(&label, &action.class, action.title.as_deref()),
+        fields,
+    )
+}
+
+/// Add the classes and title of an element to its label.
+fn describe(label: &str, class: &[String], title: Option<&str>) -> String {
+    let mut result = label.to_owned();
+    if !class.is_empty() {
+        let _ = write!(result, " [{}]", class.join(", "));
+    }
+    if let Some(title) = title {
+        let _ = write!(result, " \"{title}\"");
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+
+    #[test]
+    fn render_example() {
+        let document = serde_json::from_value(crate::example()).unwrap();
+
+        check!(
+            render(&document)
+                == "\
+order
+├── properties
+│   ├── orderNumber: 42
+│   ├── itemCount: 3
+│   └── status: \"pending\"
+├── entities
+│   ├── http://x.io/rels/order-items → http://api.x.io/orders/42/items [items, collection]
+│   └── http://x.io/rels/customer [info, customer]
+│       ├── properties
+│       │   ├── customerId: \"pj123\"
+│       │   └── name: \"Peter Joseph\"
+│       └── links
+│           └── self → http://api.x.io/customers/pj123
+├── links
+│   ├── self → http://api.x.io/orders/42
+│   ├── previous → http://api.x.io/orders/41
+│   └── next → http://api.x.io/orders/43
+└── actions
+    └── add-item: POST http://api.x.io/orders/42/items (application/x-www-form-urlencoded) \
+                   \"Add Item\"
+        ├── orderNumber (hidden) = \"42\"
+        ├── productCode (text)
+        └── quantity (number)
+"
+        );
+    }
+}
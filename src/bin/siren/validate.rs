@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use http::Method;
+use serde_json::{Map, Value};
+
+/// The input types that fields may have, as listed by the Siren specification.
+const FIELD_TYPES: &[&str] = &[
+    "hidden",
+    "text",
+    "search",
+    "tel",
+    "url",
+    "email",
+    "password",
+    "datetime",
+    "date",
+    "month",
+    "week",
+    "time",
+    "datetime-local",
+    "number",
+    "range",
+    "color",
+    "checkbox",
+    "radio",
+    "file",
+];
+
+/// Check a document against the rules of the Siren specification, returning a description of
+/// every rule that it breaks. Each description starts with the JSON path of the offending value.
+pub fn validate(document: &Value) -> Vec<String> {
+    let mut errors = vec![];
+    validate_entity(document, "$", false, &mut errors);
+
+    errors
+}
+
+/// Validate an entity, either the document itself or an embedded representation.
+fn validate_entity(entity: &Value, path: &str, embedded: bool, errors: &mut Vec<String>) {
+    let entity = match entity.as_object() {
+        Some(entity) => entity,
+        None => {
+            errors.push(format!("{path} must be an object"));
+            return;
+        },
+    };
+
+    check_strings(entity, "class", path, false, errors);
+    check_strings(entity, "rel", path, embedded, errors);
+    check_string(entity, "title", path, false, errors);
+
+    if let Some(properties) = entity.get("properties") {
+        if !properties.is_object() {
+            errors.push(format!("{path}.properties must be an object"));
+        }
+    }
+
+    for (index, sub_entity) in array(entity, "entities", path, errors).iter().enumerate() {
+        let sub_path = format!("{path}.entities[{index}]");
+
+        if sub_entity.get("href").is_some() {
+            validate_link(sub_entity, &sub_path, errors);
+        } else {
+            validate_entity(sub_entity, &sub_path, true, errors);
+        }
+    }
+
+    for (index, link) in array(entity, "links", path, errors).iter().enumerate() {
+        validate_link(link, &format!("{path}.links[{index}]"), errors);
+    }
+
+    let mut names = HashSet::new();
+    for (index, action) in array(entity, "actions", path, errors).iter().enumerate() {
+        let action_path = format!("{path}.actions[{index}]");
+        validate_action(action, &action_path, errors);
+
+        if let Some(name) = action.get("name").and_then(Value::as_str) {
+            if !names.insert(name) {
+                errors.push(format!("{action_path}.name must be unique, but {name} is repeated"));
+            }
+        }
+    }
+}
+
+/// Validate a link, either from the links of an entity or an embedded link.
+fn validate_link(link: &Value, path: &str, errors: &mut Vec<String>) {
+    let link = match link.as_object() {
+        Some(link) => link,
+        None => {
+            errors.push(format!("{path} must be an object"));
+            return;
+        },
+    };
+
+    check_strings(link, "rel", path, true, errors);
+    check_strings(link, "class", path, false, errors);
+    check_string(link, "href", path, true, errors);
+    check_string(link, "title", path, false, errors);
+    check_string(link, "type", path, false, errors);
+}
+
+/// Validate an action.
+fn validate_action(action: &Value, path: &str, errors: &mut Vec<String>) {
+    let action = match action.as_object() {
+        Some(action) => action,
+        None => {
+            errors.push(format!("{path} must be an object"));
+            return;
+        },
+    };
+
+    check_string(action, "name", path, true, errors);
+    check_strings(action, "class", path, false, errors);
+    check_string(action, "href", path, true, errors);
+    check_string(action, "title", path, false, errors);
+    check_string(action, "type", path, false, errors);
+
+    if check_string(action, "method", path, false, errors) {
+        let method = action["method"].as_str().unwrap_or_default();
+        if Method::from_bytes(method.as_bytes()).is_err() {
+            errors.push(format!("{path}.method must be an HTTP method, but is {method}"));
+        }
+    }
+
+    let mut names = HashSet::new();
+    for (index, field) in array(action, "fields", path, errors).iter().enumerate() {
+        let field_path = format!("{path}.fields[{index}]");
+        let field = match field.as_object() {
+            Some(field) => field,
+            None => {
+                errors.push(format!("{field_path} must be an object"));
+                continue;
+            },
+        };
+
+        if check_string(field, "name", &field_path, true, errors) {
+            let name = field["name"].as_str().unwrap_or_default();
+            if !names.insert(name) {
+                errors.push(format!("{field_path}.name must be unique, but {name} is repeated"));
+            }
+        }
+        check_strings(field, "class", &field_path, false, errors);
+        check_string(field, "title", &field_path, false, errors);
+
+        if check_string(field, "type", &field_path, false, errors) {
+            let input_type = field["type"].as_str().unwrap_or_default();
+            if !FIELD_TYPES.contains(&input_type) {
+                errors.push(format!(
+                    "{field_path}.type must be an HTML input type, but is {input_type}"
+                ));
+            }
+        }
+    }
+}
+
+/// Check that a member, if present, is a string. Returns whether it is present and valid.
+fn check_string(
+    object: &Map<String, Value>,
+    key: &str,
+    path: &str,
+    required: bool,
+    errors: &mut Vec<String>,
+) -> bool {
+    match object.get(key) {
+        Some(Value::String(_)) => true,
+        None if !required => false,
+        None => {
+            errors.push(format!("{path}.{key} is required"));
+            false
+        },
+        Some(_) => {
+            errors.push(format!("{path}.{key} must be a string"));
+            false
+        },
+    }
+}
+
+/// Check that a member, if present, is an array of strings. Required members must also not be
+/// empty.
+fn check_strings(
+    object: &Map<String, Value>,
+    key: &str,
+    path: &str,
+    required: bool,
+    errors: &mut Vec<String>,
+) {
+    match object.get(key) {
+        None if required => errors.push(format!("{path}.{key} is required")),
+        None => {},
+        Some(Value::Array(values)) if required && values.is_empty() => {
+            errors.push(format!("{path}.{key} must not be empty"));
+        },
+        Some(Value::Array(values)) if values.iter().all(Value::is_string) => {},
+        Some(_) => errors.push(format!("{path}.{key} must be an array of strings")),
+    }
+}
+
+/// Get a member that, if present, must be an array.
+fn array<'a>(
+    object: &'a Map<String, Value>,
+    key: &str,
+    path: &str,
+    errors: &mut Vec<String>,
+) -> &'a [Value] {
+    match object.get(key) {
+        None => &[],
+        Some(Value::Array(values)) => values,
+        Some(_) => {
+            errors.push(format!("{path}.{key} must be an array"));
+            &[]
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn valid_document() {
+        check!(validate(&crate::example()).is_empty());
+    }
+
+    #[test]
+    fn invalid_document() {
+        let document = json!({
+            "class": "order",
+            "properties": [],
+            "entities": [
+                { "href": "/orders/42/items" },
+                { "rel": [], "properties": {} }
+            ],
+            "links": [{ "rel": ["self"], "href": 42 }],
+            "actions": [
+                {
+                    "name": "add-item",
+                    "href": "/orders/42/items",
+                    "method": "NOT A METHOD",
+                    "fields": [
+                        { "name": "quantity", "type": "integer" },
+                        { "name": "quantity" },
+                        { "type": "text" }
+                    ]
+                },
+                { "name": "add-item" }
+            ]
+        });
+
+        check!(
+            validate(&document)
+                == vec![
+                    "$.class must be an array of strings",
+                    "$.properties must be an object",
+                    "$.entities[0].rel is required",
+                    "$.entities[1].rel must not be empty",
+                    "$.links[0].href must be a string",
+                    "$.actions[0].method must be an HTTP method, but is NOT A METHOD",
+                    "$.actions[0].fields[0].type must be an HTML input type, but is integer",
+                    "$.actions[0].fields[1].name must be unique, but quantity is repeated",
+                    "$.actions[0].fields[2].name is required",
+                    "$.actions[1].href is required",
+                    "$.actions[1].name must be unique, but add-item is repeated",
+                ]
+        );
+    }
+
+    #[test]
+    fn not_an_object() {
+        check!(validate(&json!([])) == vec!["$ must be an object"]);
+    }
+}
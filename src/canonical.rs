@@ -0,0 +1,253 @@
+//! Canonical serialization of Siren documents, following the JSON Canonicalization Scheme from
+//! [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785).
+//!
+//! On top of the JSON canonicalization, the `class` and `rel` members of every part of the
+//! document are sorted, since Siren treats them as unordered sets. The properties of the document
+//! and the values of fields are left in their original order, since they belong to the
+//! application.
+
+use std::fmt::Write;
+
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+use crate::Document;
+
+impl<T> Document<T>
+where
+    T: Serialize,
+{
+    /// Serialize this document to canonical JSON.
+    ///
+    /// The output is deterministic: two documents with the same content always produce exactly
+    /// the same bytes, regardless of the order in which their members were built and of whether
+    /// `serde_json` preserves the order of object members. This makes it suitable for hashing,
+    /// signing and stable snapshots.
+    ///
+    /// - Object members are sorted by the UTF-16 code units of their keys.
+    /// - Numbers are formatted as ECMAScript does, so `1.0` becomes `1` and `1e21` stays `1e+21`.
+    /// - No insignificant whitespace is produced.
+    /// - The `class` and `rel` members of the document, its entities, links, actions and fields are
+    ///   sorted.
+    ///
+    /// # Errors
+    /// If the document can not be serialized to JSON, or contains numbers that are not finite.
+    pub fn to_canonical_vec(&self) -> serde_json::Result<Vec<u8>> {
+        self.to_canonical_string().map(String::into_bytes)
+    }
+
+    /// Serialize this document to a canonical JSON string. See
+    /// [`Document::to_canonical_vec`] for details.
+    ///
+    /// # Errors
+    /// If the document can not be serialized to JSON, or contains numbers that are not finite.
+    pub fn to_canonical_string(&self) -> serde_json::Result<String> {
+        let mut value = serde_json::to_value(self)?;
+        sort_sets(&mut value);
+
+        let mut result = String::new();
+        write_value(&value, &mut result)?;
+
+        Ok(result)
+    }
+}
+
+/// Sort the `class` and `rel` members of a serialized entity, and of everything nested within it.
+/// The `properties` of the entity are not touched.
+fn sort_sets(entity: &mut Value) {
+    let members = match entity {
+        Value::Object(members) => members,
+        _ => return,
+    };
+
+    for (key, value) in members.iter_mut() {
+        match (key.as_str(), value) {
+            ("class" | "rel", Value::Array(values)) => {
+                values.sort_by(|a, b| match (a, b) {
+                    (Value::String(a), Value::String(b)) => a.encode_utf16().cmp(b.encode_utf16()),
+                    _ => std::cmp::Ordering::Equal,
+                });
+            },
+            ("entities" | "links" | "actions" | "fields", Value::Array(values)) => {
+                values.iter_mut().for_each(sort_sets);
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Write a single JSON value to the output in canonical form.
+fn write_value(value: &Value, out: &mut String) -> serde_json::Result<()> {
+    match value {
+        Value::Number(number) => write_number(number, out)?,
+        Value::Array(values) => {
+            out.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_value(value, out)?;
+            }
+            out.push(']');
+        },
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (index, (key, value)) in members.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key)?);
+                out.push(':');
+                write_value(value, out)?;
+            }
+            out.push('}');
+        },
+        scalar => out.push_str(&serde_json::to_string(scalar)?),
+    }
+
+    Ok(())
+}
+
+/// The largest integer that can be represented exactly by an IEEE 754 double.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Write a number to the output in the format used by ECMAScript's `Number.prototype.toString`.
+fn write_number(number: &Number, out: &mut String) -> serde_json::Result<()> {
+    if let Some(value) = number.as_u64().filter(|v| *v <= MAX_SAFE_INTEGER) {
+        // Writing to a `String` can not fail.
+        let _ = write!(out, "{value}");
+        return Ok(());
+    }
+
+    if let Some(value) = number
+        .as_i64()
+        .filter(|v| v.unsigned_abs() <= MAX_SAFE_INTEGER)
+    {
+        let _ = write!(out, "{value}");
+        return Ok(());
+    }
+
+    match number.as_f64().filter(|v| v.is_finite()) {
+        Some(value) => {
+            write_double(value, out);
+            Ok(())
+        },
+        None => Err(serde::ser::Error::custom("Numbers must be finite")),
+    }
+}
+
+/// Write a finite double to the output in the format used by ECMAScript's
+/// `Number.prototype.toString`.
+fn write_double(value: f64, out: &mut String) {
+    if value == 0.0 {
+        out.push('0');
+        return;
+    }
+
+    if value < 0.0 {
+        out.push('-');
+    }
+
+    // The scientific formatting produces the shortest digits that round-trip, as `d.ddde±x`.
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("Scientific formatting always has an exponent");
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().expect("Exponents are always integers");
+
+    let k = i32::try_from(digits.len()).expect("Doubles have few digits");
+    let n = exponent + 1;
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat('0').take((n - k).unsigned_abs() as usize));
+    } else if 0 < n && n <= 21 {
+        let (integer, fraction) = digits.split_at(n.unsigned_abs() as usize);
+        out.push_str(integer);
+        out.push('.');
+        out.push_str(fraction);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat('0').take(n.unsigned_abs() as usize));
+        out.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            out.push('.');
+            out.push_str(rest);
+        }
+        let _ = write!(out, "e{}{}", if n > 0 { '+' } else { '-' }, (n - 1).abs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Action, Link};
+
+    fn canonical(value: &Value) -> String {
+        let mut result = String::new();
+        write_value(value, &mut result).unwrap();
+
+        result
+    }
+
+    #[test]
+    fn numbers() {
+        check!(canonical(&json!(0)) == "0");
+        check!(canonical(&json!(-0.0)) == "0");
+        check!(canonical(&json!(42)) == "42");
+        check!(canonical(&json!(-42)) == "-42");
+        check!(canonical(&json!(1.0)) == "1");
+        check!(canonical(&json!(1.5)) == "1.5");
+        check!(canonical(&json!(0.000_001)) == "0.000001");
+        check!(canonical(&json!(0.000_000_1)) == "1e-7");
+        check!(canonical(&json!(1e21)) == "1e+21");
+        check!(canonical(&json!(1e20)) == "100000000000000000000");
+        check!(canonical(&json!(123.456e-10)) == "1.23456e-8");
+        check!(canonical(&json!(u64::MAX)) == "18446744073709552000");
+        check!(canonical(&json!(9_007_199_254_740_991_u64)) == "9007199254740991");
+    }
+
+    #[test]
+    fn sorted_keys() {
+        // "\u{1f600}" sorts before "\u{e000}" in UTF-16, but after it in UTF-8.
+        let value = json!({"b": [{"z": 1, "a": null}], "a": "x", "\u{1f600}": 1, "\u{e000}": 2});
+
+        check!(
+            canonical(&value)
+                == concat!(r#"{"a":"x","b":[{"a":null,"z":1}],"#, "\"\u{1f600}\":1,\"\u{e000}\":2}")
+        );
+    }
+
+    #[test]
+    fn canonical_document() {
+        let document = Document::new(json!({"status": "pending", "class": ["b", "a"]}))
+            .with_class("order")
+            .with_class("archived")
+            .with_link(Link::new("/orders/42").with_rel("self").with_rel("canonical"))
+            .with_action(Action::new("cancel", "/orders/42").with_class("z").with_class("y"));
+
+        check!(
+            document.to_canonical_string().unwrap()
+                == concat!(
+                    r#"{"actions":[{"class":["y","z"],"href":"/orders/42","name":"cancel"}],"#,
+                    r#""class":["archived","order"],"#,
+                    r#""links":[{"href":"/orders/42","rel":["canonical","self"]}],"#,
+                    r#""properties":{"class":["b","a"],"status":"pending"}}"#
+                )
+        );
+        check!(
+            document.to_canonical_vec().unwrap()
+                == document.to_canonical_string().unwrap().into_bytes()
+        );
+    }
+}
@@ -0,0 +1,860 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    fmt::{Debug, Display, Write},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{uri, Action, Document, Entity, Link};
+
+/// Boxed future returned by the fetcher of a crawler.
+type FetchFuture = Pin<Box<dyn Future<Output = Result<Document<Value>, String>> + Send>>;
+
+/// Type-erased fetcher for the documents at a URL.
+type BoxedFetcher = Arc<dyn Fn(String) -> FetchFuture + Send + Sync>;
+
+/// Crawler that maps out the state space of a Siren API, starting from an entry URL and following
+/// links and embedded links. See [`Crawler::crawl`].
+///
+/// The crawler doesn't make any HTTP requests itself. Instead, it is given a fetcher that
+/// retrieves the document at a URL, using whichever HTTP client is appropriate.
+///
+/// ```rust
+/// # use http_siren::{Crawler, Document, Link};
+/// # use serde_json::{json, Value};
+/// # async fn example() {
+/// let crawler = Crawler::new(|url: String| async move {
+///     match url.as_str() {
+///         "http://api.x.io/orders/42" => Ok(Document::new(json!({"orderNumber": 42}))
+///             .with_class("order")
+///             .with_link(Link::new("http://api.x.io/orders/42").with_rel("self"))
+///             .with_link(Link::new("/orders/43").with_rel("next"))),
+///         _ => Err("404 Not Found"),
+///     }
+/// })
+/// .with_max_depth(5);
+///
+/// let site_map = crawler.crawl("http://api.x.io/orders/42").await;
+/// println!("{}", site_map.to_dot());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Crawler {
+    fetch:     BoxedFetcher,
+    max_depth: usize,
+}
+
+impl Debug for Crawler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Crawler")
+            .field("max_depth", &self.max_depth)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Crawler {
+    /// Create a new crawler. By default, resources up to 3 links away from the entry URL are
+    /// fetched.
+    ///
+    /// # Parameters
+    /// - `fetch` - Async callback to fetch the document at a URL. If this fails then the resource
+    ///   is recorded as broken, with the error as the reason.
+    #[must_use]
+    pub fn new<F, Fut, E>(fetch: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Document<Value>, E>> + Send + 'static,
+        E: Display,
+    {
+        let fetch: BoxedFetcher = Arc::new(move |url| {
+            let future = fetch(url);
+            Box::pin(async move { future.await.map_err(|e| e.to_string()) })
+        });
+
+        Self {
+            fetch,
+            max_depth: 3,
+        }
+    }
+
+    /// Specify how far from the entry URL to crawl. A depth of zero fetches only the entry URL, 1
+    /// also fetches the resources that it links to, and so on. Resources that are linked to from
+    /// beyond this depth are recorded, but not fetched.
+    ///
+    /// # Parameters
+    /// - `value` - The depth limit.
+    #[must_use]
+    pub fn with_max_depth(mut self, value: usize) -> Self {
+        self.max_depth = value;
+
+        self
+    }
+
+    /// Crawl the API, starting from the given URL.
+    ///
+    /// Resources are fetched breadth first, and each URL is fetched at most once. Links and
+    /// embedded links are followed, and relative URLs are resolved against the URL of the
+    /// resource that they appear in. Embedded representations are recorded as resources in their
+    /// own right, identified by their self link if they have one, and their links are followed as
+    /// well, unless a resource with that URL was already found, in which case it is fetched or
+    /// recorded on its own instead. Actions are recorded, but never followed, since submitting
+    /// them may have side effects.
+    ///
+    /// # Parameters
+    /// - `entry` - The URL to start crawling from.
+    pub async fn crawl<U>(&self, entry: U) -> SiteMap
+    where
+        U: ToString,
+    {
+        let entry = entry.to_string();
+        let mut site_map = SiteMap {
+            entry:     entry.clone(),
+            resources: vec![],
+            edges:     vec![],
+            cycles:    vec![],
+            index:     HashMap::new(),
+        };
+        site_map.discover(&entry, 0);
+
+        let mut queue = VecDeque::from([(entry, 0)]);
+        while let Some((url, depth)) = queue.pop_front() {
+            if depth > self.max_depth || site_map.resource(&url).state != ResourceState::NotVisited
+            {
+                continue;
+            }
+
+            match (self.fetch)(url.clone()).await {
+                Ok(document) => {
+                    let mut discovered = vec![];
+                    site_map.record(
+                        &url,
+                        &url,
+                        depth,
+                        &document.class,
+                        &document.entities,
+                        &document.links,
+                        &document.actions,
+                        &mut discovered,
+                    );
+                    queue.extend(discovered.into_iter().map(|url| (url, depth + 1)));
+                },
+                Err(error) => {
+                    site_map.resource_mut(&url).state = ResourceState::Broken { error };
+                },
+            }
+        }
+
+        site_map.cycles = site_map.find_cycles();
+
+        site_map
+    }
+}
+
+/// Map of the resources of an API and the links between them, as produced by a [`Crawler`].
+///
+/// This serializes to a JSON graph, with the resources as the nodes, and can be rendered as a
+/// Graphviz graph with [`SiteMap::to_dot`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SiteMap {
+    /// The URL that the crawl started from.
+    pub entry:     String,
+    /// Every resource that was encountered, in the order they were discovered.
+    pub resources: Vec<Resource>,
+    /// Every link, embedded entity and action that was encountered.
+    pub edges:     Vec<Edge>,
+    /// Cycles in the links between resources, each starting and ending with the same URL. Links
+    /// from a resource to itself, such as self links, are not counted as cycles.
+    pub cycles:    Vec<Vec<String>>,
+    #[serde(skip)]
+    index:         HashMap<String, usize>,
+}
+
+/// A single resource encountered while crawling.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Resource {
+    /// The URL of the resource. Embedded representations without a self link are identified by
+    /// the URL of the entity they are embedded in, with a fragment giving their position.
+    pub url:     String,
+    /// The number of links between the entry URL and this resource.
+    pub depth:   usize,
+    /// Whether the resource was fetched.
+    #[serde(flatten)]
+    pub state:   ResourceState,
+    /// The classes of the resource.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub class:   Vec<String>,
+    /// The names of the actions of the resource.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<String>,
+}
+
+/// Whether a resource was fetched while crawling.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum ResourceState {
+    /// The resource was fetched, or was embedded in one that was.
+    Visited,
+    /// The resource was beyond the depth limit of the crawler, so wasn't fetched.
+    NotVisited,
+    /// Fetching the resource failed.
+    Broken {
+        /// The reason that fetching the resource failed.
+        error: String,
+    },
+}
+
+/// A single link between two resources.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Edge {
+    /// The URL of the resource that the link appears in.
+    pub from: String,
+    /// The URL that the link points to.
+    pub to:   String,
+    /// What kind of link this is.
+    #[serde(flatten)]
+    pub kind: EdgeKind,
+}
+
+/// The kinds of link between resources.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum EdgeKind {
+    /// A link of the resource.
+    Link {
+        /// The link relations of the link.
+        rel: Vec<String>,
+    },
+    /// An embedded link of the resource.
+    EmbeddedLink {
+        /// The link relations of the embedded link.
+        rel: Vec<String>,
+    },
+    /// An embedded representation of the resource.
+    EmbeddedRepresentation {
+        /// The link relations of the embedded representation.
+        rel: Vec<String>,
+    },
+    /// An action of the resource, pointing to the URL that it is submitted to.
+    Action {
+        /// The name of the action.
+        name:   String,
+        /// The HTTP method of the action.
+        method: String,
+    },
+}
+
+impl EdgeKind {
+    /// The link relations of this link, which is empty for actions.
+    #[must_use]
+    pub fn rel(&self) -> &[String] {
+        match self {
+            Self::Link { rel }
+            | Self::EmbeddedLink { rel }
+            | Self::EmbeddedRepresentation { rel } => rel,
+            Self::Action { .. } => &[],
+        }
+    }
+}
+
+impl SiteMap {
+    /// Get a resource that was encountered while crawling.
+    ///
+    /// # Parameters
+    /// - `url` - The URL of the resource.
+    #[must_use]
+    pub fn get(&self, url: &str) -> Option<&Resource> {
+        self.index.get(url).map(|&index| &self.resources[index])
+    }
+
+    /// Every class of every resource that was encountered.
+    #[must_use]
+    pub fn classes(&self) -> BTreeSet<&str> {
+        self.resources
+            .iter()
+            .flat_map(|resource| &resource.class)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Every link relation that was encountered.
+    #[must_use]
+    pub fn rels(&self) -> BTreeSet<&str> {
+        self.edges
+            .iter()
+            .flat_map(|edge| edge.kind.rel())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Every action name that was encountered.
+    #[must_use]
+    pub fn actions(&self) -> BTreeSet<&str> {
+        self.resources
+            .iter()
+            .flat_map(|resource| &resource.actions)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The links and embedded links that point to resources that couldn't be fetched.
+    #[must_use]
+    pub fn broken_links(&self) -> Vec<&Edge> {
+        self.edges
+            .iter()
+            .filter(|edge| !matches!(edge.kind, EdgeKind::Action { .. }))
+            .filter(|edge| {
+                self.get(&edge.to).map_or(false, |resource| {
+                    matches!(resource.state, ResourceState::Broken { .. })
+                })
+            })
+            .collect()
+    }
+
+    /// Render this site map as a Graphviz graph in the DOT language.
+    ///
+    /// Resources are labelled with their classes and URL. Resources that weren't fetched are
+    /// dashed, and broken ones are red. Embedded representations are drawn with bold edges,
+    /// embedded links with dotted edges and actions with dashed blue edges.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut output = "digraph siren {\n".to_owned();
+
+        for resource in &self.resources {
+            let mut label = resource.url.clone();
+            if !resource.class.is_empty() {
+                label = format!("{}\n{label}", resource.class.join(", "));
+            }
+            let style = match &resource.state {
+                ResourceState::Visited => String::new(),
+                ResourceState::NotVisited => ", style=dashed".to_owned(),
+                ResourceState::Broken { error } => {
+                    label = format!("{label}\n{error}");
+                    ", color=red".to_owned()
+                },
+            };
+
+            let _ = writeln!(
+                output,
+                "    {} [label={}{style}];",
+                quote(&resource.url),
+                quote(&label)
+            );
+        }
+
+        for edge in &self.edges {
+            let (label, style) = match &edge.kind {
+                EdgeKind::Link { rel } => (rel.join(", "), ""),
+                EdgeKind::EmbeddedLink { rel } => (rel.join(", "), ", style=dotted"),
+                EdgeKind::EmbeddedRepresentation { rel } => (rel.join(", "), ", style=bold"),
+                EdgeKind::Action { name, method } => {
+                    (format!("{name} ({method})"), ", style=dashed, color=blue")
+                },
+            };
+
+            let _ = writeln!(
+                output,
+                "    {} -> {} [label={}{style}];",
+                quote(&edge.from),
+                quote(&edge.to),
+                quote(&label)
+            );
+        }
+
+        output.push_str("}\n");
+
+        output
+    }
+
+    /// Record a resource, if it hasn't already been encountered. Returns whether it is new.
+    fn discover(&mut self, url: &str, depth: usize) -> bool {
+        if self.index.contains_key(url) {
+            return false;
+        }
+
+        self.index.insert(url.to_owned(), self.resources.len());
+        self.resources.push(Resource {
+            url: url.to_owned(),
+            depth,
+            state: ResourceState::NotVisited,
+            class: vec![],
+            actions: vec![],
+        });
+
+        true
+    }
+
+    fn resource(&self, url: &str) -> &Resource {
+        &self.resources[self.index[url]]
+    }
+
+    fn resource_mut(&mut self, url: &str) -> &mut Resource {
+        &mut self.resources[self.index[url]]
+    }
+
+    /// Record the contents of an entity, adding the URLs of newly discovered resources that should
+    /// be fetched to `discovered`.
+    #[allow(clippy::too_many_arguments)]
+    fn record(
+        &mut self,
+        url: &str,
+        base: &str,
+        depth: usize,
+        class: &[String],
+        entities: &[Entity],
+        links: &[Link],
+        actions: &[Action],
+        discovered: &mut Vec<String>,
+    ) {
+        let resource = self.resource_mut(url);
+        resource.state = ResourceState::Visited;
+        resource.class = class.to_vec();
+        resource.actions = actions.iter().map(|action| action.name.clone()).collect();
+
+        for link in links {
+            let to = resolve_href(base, &link.href);
+            self.follow(url, to, depth, EdgeKind::Link { rel: link.rel.clone() }, discovered);
+        }
+
+        for (index, entity) in entities.iter().enumerate() {
+            match entity {
+                Entity::Link(link) => {
+                    let to = resolve_href(base, &link.href);
+                    let kind = EdgeKind::EmbeddedLink {
+                        rel: link.rel.clone(),
+                    };
+                    self.follow(url, to, depth, kind, discovered);
+                },
+                Entity::Representation(representation) => {
+                    let to = representation
+                        .links
+                        .iter()
+                        .find(|link| link.rel.iter().any(|rel| rel == "self"))
+                        .map_or_else(
+                            || format!("{url}#entities[{index}]"),
+                            |link| resolve_href(base, &link.href),
+                        );
+
+                    self.edges.push(Edge {
+                        from: url.to_owned(),
+                        to:   to.clone(),
+                        kind: EdgeKind::EmbeddedRepresentation {
+                            rel: representation.rel.clone(),
+                        },
+                    });
+
+                    // A resource that was already found, for example through a link, is fetched or
+                    // recorded on its own rather than described by what is embedded here.
+                    if self.discover(&to, depth) {
+                        self.record(
+                            &to,
+                            base,
+                            depth,
+                            &representation.class,
+                            &representation.entities,
+                            &representation.links,
+                            &representation.actions,
+                            discovered,
+                        );
+                    }
+                },
+            }
+        }
+
+        for action in actions {
+            self.edges.push(Edge {
+                from: url.to_owned(),
+                to:   resolve_href(base, &action.href),
+                kind: EdgeKind::Action {
+                    name:   action.name.clone(),
+                    method: action.method().to_string(),
+                },
+            });
+        }
+    }
+
+    /// Record a link to another resource, which is to be fetched if it hasn't been seen before.
+    fn follow(
+        &mut self,
+        from: &str,
+        to: String,
+        depth: usize,
+        kind: EdgeKind,
+        discovered: &mut Vec<String>,
+    ) {
+        if self.discover(&to, depth + 1) {
+            discovered.push(to.clone());
+        }
+
+        self.edges.push(Edge {
+            from: from.to_owned(),
+            to,
+            kind,
+        });
+    }
+
+    /// Find the cycles in the links between resources, using a depth first search from each
+    /// resource in turn.
+    fn find_cycles(&self) -> Vec<Vec<String>> {
+        let mut adjacent: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            if edge.from != edge.to && !matches!(edge.kind, EdgeKind::Action { .. }) {
+                let targets = adjacent.entry(&edge.from).or_default();
+                if !targets.contains(&edge.to.as_str()) {
+                    targets.push(&edge.to);
+                }
+            }
+        }
+
+        let mut search = CycleSearch {
+            adjacent,
+            finished: BTreeSet::new(),
+            stack: vec![],
+            cycles: vec![],
+        };
+        for resource in &self.resources {
+            search.visit(&resource.url);
+        }
+
+        search.cycles
+    }
+}
+
+/// State of the search for cycles in a site map.
+struct CycleSearch<'a> {
+    adjacent: HashMap<&'a str, Vec<&'a str>>,
+    finished: BTreeSet<&'a str>,
+    stack:    Vec<&'a str>,
+    cycles:   Vec<Vec<String>>,
+}
+
+impl<'a> CycleSearch<'a> {
+    fn visit(&mut self, url: &'a str) {
+        if self.finished.contains(url) {
+            return;
+        }
+        if let Some(start) = self.stack.iter().position(|&entry| entry == url) {
+            let mut cycle = self.stack[start..]
+                .iter()
+                .map(|&url| url.to_owned())
+                .collect::<Vec<_>>();
+            cycle.push(url.to_owned());
+            self.cycles.push(cycle);
+            return;
+        }
+
+        self.stack.push(url);
+        for target in self.adjacent.get(url).cloned().unwrap_or_default() {
+            self.visit(target);
+        }
+        self.stack.pop();
+        self.finished.insert(url);
+    }
+}
+
+/// Resolve a link target against the URL of the resource it appears in, as described by
+/// RFC 3986. Fragments are dropped, since they identify a part of a resource rather than another
+/// resource to fetch.
+fn resolve_href(base: &str, href: &str) -> String {
+    let mut url = uri::resolve(base, href);
+    if let Some(fragment) = url.find('#') {
+        url.truncate(fragment);
+    }
+
+    url
+}
+
+/// Quote a string for use as an ID or label in the DOT language.
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, convert::Infallible, net::SocketAddr};
+
+    use assert2::check;
+    use http::Method;
+    use serde_json::json;
+
+    use super::*;
+    use crate::EmbeddedRepresentation;
+
+    /// Fixtures for a small API of orders, in which order 43 is missing.
+    fn fixtures() -> HashMap<String, Document<Value>> {
+        HashMap::from([
+            (
+                "http://api.x.io/orders/42".to_owned(),
+                Document::new(json!({"orderNumber": 42}))
+                    .with_class("order")
+                    .with_link(Link::new("/orders/42").with_rel("self"))
+                    .with_link(Link::new("/orders/41").with_rel("previous"))
+                    .with_link(Link::new("/orders/43").with_rel("next"))
+                    .with_embedded_link(Link::new("items").with_rel("items"))
+                    .with_embedded_representation(
+                        EmbeddedRepresentation::new(json!({"customerId": "pj123"}))
+                            .with_rel("customer")
+                            .with_class("customer")
+                            .with_link(Link::new("/customers/pj123").with_rel("self")),
+                    )
+                    .with_action(
+                        Action::new("add-item", "/orders/42/items").with_method(Method::POST),
+                    ),
+            ),
+            (
+                "http://api.x.io/orders/41".to_owned(),
+                Document::new(json!({"orderNumber": 41}))
+                    .with_class("order")
+                    .with_link(Link::new("/orders/42").with_rel("next")),
+            ),
+            (
+                "http://api.x.io/orders/items".to_owned(),
+                Document::new(json!({}))
+                    .with_class("items")
+                    .with_class("collection")
+                    .with_link(Link::new("/products/1").with_rel("product")),
+            ),
+        ])
+    }
+
+    fn crawler() -> Crawler {
+        let fixtures = fixtures();
+
+        Crawler::new(move |url: String| {
+            let document = fixtures.get(&url).cloned();
+            async move { document.ok_or("404 Not Found") }
+        })
+    }
+
+    #[tokio::test]
+    async fn crawl() {
+        let site_map = crawler().with_max_depth(1).crawl("http://api.x.io/orders/42").await;
+
+        let states = site_map
+            .resources
+            .iter()
+            .map(|resource| (resource.url.as_str(), resource.depth, resource.state.clone()))
+            .collect::<Vec<_>>();
+        check!(
+            states
+                == vec![
+                    ("http://api.x.io/orders/42", 0, ResourceState::Visited),
+                    ("http://api.x.io/orders/41", 1, ResourceState::Visited),
+                    (
+                        "http://api.x.io/orders/43",
+                        1,
+                        ResourceState::Broken {
+                            error: "404 Not Found".to_owned(),
+                        }
+                    ),
+                    ("http://api.x.io/orders/items", 1, ResourceState::Visited),
+                    ("http://api.x.io/customers/pj123", 0, ResourceState::Visited),
+                    ("http://api.x.io/products/1", 2, ResourceState::NotVisited),
+                ]
+        );
+
+        check!(site_map.classes() == BTreeSet::from(["collection", "customer", "items", "order"]));
+        check!(
+            site_map.rels()
+                == BTreeSet::from(["customer", "items", "next", "previous", "product", "self"])
+        );
+        check!(site_map.actions() == BTreeSet::from(["add-item"]));
+
+        let broken = site_map.broken_links();
+        check!(broken.len() == 1);
+        check!(broken[0].from == "http://api.x.io/orders/42");
+        check!(broken[0].to == "http://api.x.io/orders/43");
+
+        check!(
+            site_map.cycles
+                == vec![vec![
+                    "http://api.x.io/orders/42",
+                    "http://api.x.io/orders/41",
+                    "http://api.x.io/orders/42",
+                ]]
+        );
+    }
+
+    #[tokio::test]
+    async fn depth_limit() {
+        let site_map = crawler().with_max_depth(0).crawl("http://api.x.io/orders/42").await;
+
+        check!(site_map.get("http://api.x.io/orders/42").unwrap().state == ResourceState::Visited);
+        check!(
+            site_map.get("http://api.x.io/orders/41").unwrap().state == ResourceState::NotVisited
+        );
+        check!(site_map.get("http://api.x.io/products/1") == None);
+        check!(site_map.cycles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn to_dot() {
+        let site_map = crawler().with_max_depth(0).crawl("http://api.x.io/orders/41").await;
+
+        check!(
+            site_map.to_dot()
+                == "\
+digraph siren {
+    \"http://api.x.io/orders/41\" [label=\"order\\nhttp://api.x.io/orders/41\"];
+    \"http://api.x.io/orders/42\" [label=\"http://api.x.io/orders/42\", style=dashed];
+    \"http://api.x.io/orders/41\" -> \"http://api.x.io/orders/42\" [label=\"next\"];
+}
+"
+        );
+    }
+
+    #[tokio::test]
+    async fn to_json() {
+        let site_map = crawler().with_max_depth(0).crawl("http://api.x.io/orders/42").await;
+        let json = serde_json::to_value(&site_map).unwrap();
+
+        check!(json["entry"] == json!("http://api.x.io/orders/42"));
+        check!(
+            json["resources"][0]
+                == json!({
+                    "url": "http://api.x.io/orders/42",
+                    "depth": 0,
+                    "state": "visited",
+                    "class": ["order"],
+                    "actions": ["add-item"]
+                })
+        );
+        check!(
+            json["edges"][1]
+                == json!({
+                    "from": "http://api.x.io/orders/42",
+                    "to": "http://api.x.io/orders/41",
+                    "kind": "link",
+                    "rel": ["previous"]
+                })
+        );
+        check!(
+            json["edges"][6]
+                == json!({
+                    "from": "http://api.x.io/orders/42",
+                    "to": "http://api.x.io/orders/42/items",
+                    "kind": "action",
+                    "name": "add-item",
+                    "method": "POST"
+                })
+        );
+    }
+
+    #[tokio::test]
+    async fn local_server() {
+        let service = hyper::service::make_service_fn(|_| async {
+            Ok::<_, Infallible>(hyper::service::service_fn(
+                |request: hyper::Request<hyper::Body>| async move {
+                    let document = match request.uri().path() {
+                        "/" => Document::new(json!({}))
+                            .with_class("root")
+                            .with_link(Link::new("/orders").with_rel("orders"))
+                            .with_link(Link::new("/missing").with_rel("missing")),
+                        "/orders" => Document::new(json!({}))
+                            .with_class("orders")
+                            .with_link(Link::new("/").with_rel("up")),
+                        _ => {
+                            return Ok::<_, Infallible>(
+                                hyper::Response::builder()
+                                    .status(404)
+                                    .body(hyper::Body::empty())
+                                    .unwrap(),
+                            )
+                        },
+                    };
+
+                    Ok(hyper::Response::new(hyper::Body::from(
+                        serde_json::to_vec(&document).unwrap(),
+                    )))
+                },
+            ))
+        });
+        let server = hyper::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(service);
+        let entry = format!("http://{}/", server.local_addr());
+        tokio::spawn(server);
+
+        let crawler = Crawler::new(|url: String| async move {
+            let response = hyper::Client::new()
+                .get(url.parse().map_err(|e: http::uri::InvalidUri| e.to_string())?)
+                .await
+                .map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(response.status().to_string());
+            }
+
+            let body = hyper::body::to_bytes(response.into_body())
+                .await
+                .map_err(|e| e.to_string())?;
+            serde_json::from_slice(&body).map_err(|e| e.to_string())
+        });
+
+        let site_map = crawler.crawl(&entry).await;
+
+        check!(site_map.classes() == BTreeSet::from(["orders", "root"]));
+        check!(site_map.broken_links().len() == 1);
+        check!(site_map.broken_links()[0].to == format!("{entry}missing"));
+        check!(site_map.cycles == vec![vec![entry.clone(), format!("{entry}orders"), entry]]);
+    }
+
+    #[test]
+    fn resolve_hrefs() {
+        check!(resolve_href("http://x.io/a/b", "http://y.io/c") == "http://y.io/c");
+        check!(resolve_href("http://x.io/a/b", "/c") == "http://x.io/c");
+        check!(resolve_href("http://x.io/a/b?q=1", "c") == "http://x.io/a/c");
+        check!(resolve_href("http://x.io", "c") == "http://x.io/c");
+        check!(resolve_href("/a/b", "/c") == "/c");
+        check!(resolve_href("/a/b", "c") == "/a/c");
+        check!(resolve_href("https://x.io/a/b", "//cdn.x.io/c") == "https://cdn.x.io/c");
+        check!(resolve_href("http://x.io/a/b?q=1", "?page=2") == "http://x.io/a/b?page=2");
+        check!(resolve_href("http://x.io/a/b?q=1", "#top") == "http://x.io/a/b?q=1");
+        check!(resolve_href("http://x.io/a/b", "c#top") == "http://x.io/a/c");
+        check!(resolve_href("http://x.io/a/b/c", "../d/./e") == "http://x.io/a/d/e");
+        check!(resolve_href("http://x.io/a/b", "../../c") == "http://x.io/c");
+    }
+
+    #[tokio::test]
+    async fn fetch_linked_embedded_representations() {
+        let fixtures = HashMap::from([
+            (
+                "http://api.x.io/orders/42".to_owned(),
+                Document::new(json!({"orderNumber": 42}))
+                    .with_class("order")
+                    .with_link(Link::new("/customers/pj123").with_rel("customer"))
+                    .with_embedded_representation(
+                        EmbeddedRepresentation::new(json!({"customerId": "pj123"}))
+                            .with_rel("customer")
+                            .with_class("summary")
+                            .with_link(Link::new("/customers/pj123").with_rel("self")),
+                    ),
+            ),
+            (
+                "http://api.x.io/customers/pj123".to_owned(),
+                Document::new(json!({"customerId": "pj123", "name": "Peter Joseph"}))
+                    .with_class("customer")
+                    .with_action(Action::new("rename", "/customers/pj123")),
+            ),
+        ]);
+        let crawler = Crawler::new(move |url: String| {
+            let document = fixtures.get(&url).cloned();
+            async move { document.ok_or("404 Not Found") }
+        });
+
+        let site_map = crawler.crawl("http://api.x.io/orders/42").await;
+
+        let customer = site_map.get("http://api.x.io/customers/pj123").unwrap();
+        check!(customer.depth == 1);
+        check!(customer.state == ResourceState::Visited);
+        check!(customer.class == vec!["customer"]);
+        check!(customer.actions == vec!["rename"]);
+    }
+}
@@ -1,30 +1,40 @@
 #![allow(clippy::needless_pass_by_value)]
 
-use serde::Serialize;
-use serde_json::Value;
+use http::Method;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::values::LinkRelation;
 
 /// Representation of a Siren document.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[must_use]
 pub struct Document<T>
 where
     T: Serialize,
 {
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub class:      Vec<String>,
     pub properties: T,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub entities:   Vec<Entity>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub links:      Vec<Link>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub actions:    Vec<Action>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title:      Option<String>,
+    /// Additional members that are not part of the Siren specification, such as vendor extensions.
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
 }
 
 /// Representation of an embedded entity. Either an embedded link or a full representation.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(untagged)]
 #[must_use]
 pub enum Entity {
@@ -33,31 +43,43 @@ pub enum Entity {
 }
 
 /// Body of an embedded representation.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[must_use]
 pub struct EmbeddedRepresentation {
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    rel:        Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    class:      Vec<String>,
-    properties: Value,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    entities:   Vec<Entity>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    links:      Vec<Link>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    actions:    Vec<Action>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rel:        Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub class:      Vec<String>,
+    #[serde(default)]
+    pub properties: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "openapi", schema(no_recursion))]
+    pub entities:   Vec<Entity>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links:      Vec<Link>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions:    Vec<Action>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    title:      Option<String>,
+    pub title:      Option<String>,
+    /// The permissions required to see this representation. See [`Document::authorize`].
+    #[serde(skip)]
+    pub requires:   Vec<String>,
+    /// Additional members that are not part of the Siren specification, such as vendor extensions.
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
 }
 
 /// Representation of a link. Either as a standard link or an embedded entity.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[must_use]
 pub struct Link {
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rel:        Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub class:      Vec<String>,
     pub href:       String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -65,41 +87,100 @@ pub struct Link {
     #[serde(rename = "type")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub media_type: Option<String>,
+    /// The permissions required to see this link. See [`Document::authorize`].
+    #[serde(skip)]
+    pub requires:   Vec<String>,
+    /// Additional members that are not part of the Siren specification, such as vendor extensions.
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
 }
 
 /// Representation of an action.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[must_use]
 pub struct Action {
     pub name:       String,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub class:      Vec<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub method:     Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_method", deserialize_with = "deserialize_method")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<String>))]
+    pub method:     Option<Method>,
     pub href:       String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title:      Option<String>,
     #[serde(rename = "type")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub media_type: Option<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fields:     Vec<Field>,
+    /// The permissions required to use this action. See [`Document::authorize`].
+    #[serde(skip)]
+    pub requires:   Vec<String>,
+    /// Additional members that are not part of the Siren specification, such as vendor extensions.
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
 }
 
 /// Representation of a field within an action.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[must_use]
 pub struct Field {
-    pub name:       String,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub class:      Vec<String>,
+    pub name:        String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub class:       Vec<String>,
     #[serde(rename = "type")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub input_type: Option<String>,
+    pub input_type:  Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub value:      Option<Value>,
+    pub value:       Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub title:      Option<String>,
+    pub title:       Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub required:    bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min:         Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max:         Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step:        Option<f64>,
+    #[serde(rename = "minLength")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length:  Option<usize>,
+    #[serde(rename = "maxLength")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length:  Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern:     Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub multiple:    bool,
+    /// Additional members that are not part of the Siren specification, such as vendor extensions.
+    #[serde(flatten)]
+    pub extensions:  Map<String, Value>,
+}
+
+/// Representation of one of the values that may be selected for a field, such as one of a group
+/// of radio buttons or checkboxes.
+///
+/// Following the common Siren convention, the options of a field are given as its value, which is
+/// then an array of options.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[must_use]
+pub struct FieldOption {
+    pub value:    Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title:    Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub selected: bool,
 }
 
 impl<T> Document<T>
@@ -118,6 +199,7 @@ where
             links:      vec![],
             actions:    vec![],
             title:      None,
+            extensions: Map::new(),
         }
     }
 
@@ -209,6 +291,34 @@ where
 
         self
     }
+
+    /// Get the link to the document itself, if there is one. This is the first link with a
+    /// relation of `self`.
+    #[must_use]
+    pub fn self_link(&self) -> Option<&Link> {
+        let rel = LinkRelation::SelfLink.to_string();
+
+        self.links.iter().find(|link| link.rel.contains(&rel))
+    }
+
+    /// Get the actions that target the resource this document represents. These are the actions
+    /// that have the same href as the self link of the document, or every action if the document
+    /// has no self link.
+    #[must_use]
+    pub fn resource_actions(&self) -> Vec<&Action> {
+        match self.self_link() {
+            Some(link) => self.actions.iter().filter(|a| a.href == link.href).collect(),
+            None => self.actions.iter().collect(),
+        }
+    }
+
+    /// Determine the HTTP methods that are allowed for the resource this document represents, as
+    /// would be listed in an `Allow` header. This is always `GET`, followed by the method of every
+    /// action that targets the resource in the order they were added, without duplicates.
+    #[must_use]
+    pub fn allowed_methods(&self) -> Vec<Method> {
+        allowed_methods(self.resource_actions())
+    }
 }
 
 impl EmbeddedRepresentation {
@@ -216,6 +326,9 @@ impl EmbeddedRepresentation {
     ///
     /// # Parameters
     /// - `payload` - The payload of the embedded representation.
+    ///
+    /// # Panics
+    /// If the payload can not be serialized to JSON.
     pub fn new<S>(payload: S) -> Self
     where
         S: Serialize,
@@ -230,6 +343,8 @@ impl EmbeddedRepresentation {
             links:      vec![],
             actions:    vec![],
             title:      None,
+            requires:   vec![],
+            extensions: Map::new(),
         }
     }
 
@@ -351,6 +466,8 @@ impl Link {
             href:       href.to_string(),
             title:      None,
             media_type: None,
+            requires:   vec![],
+            extensions: Map::new(),
         }
     }
 
@@ -437,6 +554,8 @@ impl Action {
             title:      None,
             media_type: None,
             fields:     vec![],
+            requires:   vec![],
+            extensions: Map::new(),
         }
     }
 
@@ -493,16 +612,39 @@ impl Action {
     /// Specify an HTTP method for the action.
     ///
     /// # Parameters
-    /// - `value` - The HTTP method to specify.
-    pub fn with_method<S>(mut self, value: S) -> Self
+    /// - `value` - The HTTP method to specify. This can be an [`http::Method`] or one of the
+    ///   [`HttpMethods`](crate::values::HttpMethods) values.
+    pub fn with_method<M>(mut self, value: M) -> Self
     where
-        S: ToString,
+        M: Into<Method>,
     {
-        self.method = Some(value.to_string());
+        self.method = Some(value.into());
 
         self
     }
 
+    /// Specify an HTTP method for the action from a value that may not be a valid method, such as
+    /// a string containing a method name.
+    ///
+    /// # Parameters
+    /// - `value` - The HTTP method to specify.
+    ///
+    /// # Errors
+    /// If the value is not a valid HTTP method.
+    pub fn try_with_method<M>(self, value: M) -> Result<Self, M::Error>
+    where
+        M: TryInto<Method>,
+    {
+        Ok(self.with_method(value.try_into()?))
+    }
+
+    /// Get the HTTP method for the action. If none has been specified then this is `GET`, as
+    /// required by the Siren specification.
+    #[must_use]
+    pub fn method(&self) -> Method {
+        self.method.clone().unwrap_or(Method::GET)
+    }
+
     /// Specify a field for the action.
     ///
     /// # Parameters
@@ -527,11 +669,21 @@ impl Field {
         S: ToString,
     {
         Self {
-            name:       name.to_string(),
-            class:      vec![],
-            input_type: None,
-            value:      None,
-            title:      None,
+            name:        name.to_string(),
+            class:       vec![],
+            input_type:  None,
+            value:       None,
+            title:       None,
+            required:    false,
+            min:         None,
+            max:         None,
+            step:        None,
+            min_length:  None,
+            max_length:  None,
+            pattern:     None,
+            placeholder: None,
+            multiple:    false,
+            extensions:  Map::new(),
         }
     }
 
@@ -574,8 +726,14 @@ impl Field {
 
     /// Specify a value for the field.
     ///
+    /// The options of a field are sent as its value, so this replaces any options specified with
+    /// [`Field::with_option`]. The two should not be used on the same field.
+    ///
     /// # Parameters
     /// - `value` - The value to specify.
+    ///
+    /// # Panics
+    /// If the value can not be serialized to JSON.
     pub fn with_value<S>(mut self, value: S) -> Self
     where
         S: Serialize,
@@ -599,6 +757,236 @@ impl Field {
 
         self
     }
+
+    /// Mark the field as one that must be given a value.
+    pub fn with_required(mut self) -> Self {
+        self.required = true;
+
+        self
+    }
+
+    /// Specify the minimum numeric value of the field.
+    ///
+    /// # Parameters
+    /// - `value` - The minimum value.
+    pub fn with_min(mut self, value: f64) -> Self {
+        self.min = Some(value);
+
+        self
+    }
+
+    /// Specify the maximum numeric value of the field.
+    ///
+    /// # Parameters
+    /// - `value` - The maximum value.
+    pub fn with_max(mut self, value: f64) -> Self {
+        self.max = Some(value);
+
+        self
+    }
+
+    /// Specify the granularity of numeric values of the field. Values must be a whole number of
+    /// steps above the minimum, or above zero if there is no minimum.
+    ///
+    /// # Parameters
+    /// - `value` - The step.
+    pub fn with_step(mut self, value: f64) -> Self {
+        self.step = Some(value);
+
+        self
+    }
+
+    /// Specify the minimum length, in characters, of the value of the field.
+    ///
+    /// # Parameters
+    /// - `value` - The minimum length.
+    pub fn with_min_length(mut self, value: usize) -> Self {
+        self.min_length = Some(value);
+
+        self
+    }
+
+    /// Specify the maximum length, in characters, of the value of the field.
+    ///
+    /// # Parameters
+    /// - `value` - The maximum length.
+    pub fn with_max_length(mut self, value: usize) -> Self {
+        self.max_length = Some(value);
+
+        self
+    }
+
+    /// Specify a regular expression that the entire value of the field must match, as for the
+    /// `pattern` attribute of an HTML input. Submissions are checked against the pattern with the
+    /// `patterns` feature. Without it, or if the pattern is not a valid regular expression, any
+    /// value submitted for the field is rejected since the pattern can not be checked.
+    ///
+    /// # Parameters
+    /// - `value` - The regular expression.
+    pub fn with_pattern<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.pattern = Some(value.to_string());
+
+        self
+    }
+
+    /// Specify a hint to display in the field when it has no value.
+    ///
+    /// # Parameters
+    /// - `value` - The placeholder to specify.
+    pub fn with_placeholder<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.placeholder = Some(value.to_string());
+
+        self
+    }
+
+    /// Mark the field as one that may be given more than one value.
+    pub fn with_multiple(mut self) -> Self {
+        self.multiple = true;
+
+        self
+    }
+
+    /// Specify one of the values that may be selected for the field. Once any options are
+    /// specified, the value of the field must be one of them.
+    ///
+    /// The options are sent as the value of the field, so this can not be combined with
+    /// [`Field::with_value`]. The current value is instead indicated by the options that are
+    /// selected.
+    ///
+    /// # Parameters
+    /// - `value` - The option to specify.
+    ///
+    /// # Panics
+    /// If the option can not be serialized to JSON, or if the field already has a value that is not
+    /// a list of options.
+    pub fn with_option<O>(mut self, value: O) -> Self
+    where
+        O: Into<FieldOption>,
+    {
+        let option = serde_json::to_value(value.into()).expect("Failed to serialize option");
+
+        if self.options().is_empty() {
+            assert!(
+                self.value.is_none(),
+                "Field `{}` already has a value, which options would replace",
+                self.name
+            );
+            self.value = Some(Value::Array(vec![]));
+        }
+        if let Some(Value::Array(options)) = &mut self.value {
+            options.push(option);
+        }
+
+        self
+    }
+
+    /// Get the options that may be selected for the field.
+    ///
+    /// Following the Siren convention, a value of the field that is a non-empty array in which
+    /// every entry is an object with a `value` member is a list of options. A plain value of that
+    /// shape can not be told apart from options, so is also returned here.
+    #[must_use]
+    pub fn options(&self) -> Vec<FieldOption> {
+        let entries = match &self.value {
+            Some(Value::Array(entries)) => entries,
+            _ => return vec![],
+        };
+
+        if entries.is_empty() || !entries.iter().all(|entry| entry.get("value").is_some()) {
+            return vec![];
+        }
+
+        entries
+            .iter()
+            .filter_map(|entry| serde_json::from_value(entry.clone()).ok())
+            .collect()
+    }
+}
+
+impl FieldOption {
+    /// Create a new field option.
+    ///
+    /// # Parameters
+    /// - `value` - The value that is submitted when this option is selected.
+    ///
+    /// # Panics
+    /// If the value can not be serialized to JSON.
+    pub fn new<S>(value: S) -> Self
+    where
+        S: Serialize,
+    {
+        Self {
+            value:    serde_json::to_value(value).expect("Failed to serialize value"),
+            title:    None,
+            selected: false,
+        }
+    }
+
+    /// Specify a label to display for the option.
+    ///
+    /// # Parameters
+    /// - `value` - The title to specify.
+    pub fn with_title<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.title = Some(value.to_string());
+
+        self
+    }
+
+    /// Mark the option as selected, making it the current value of the field.
+    pub fn with_selected(mut self) -> Self {
+        self.selected = true;
+
+        self
+    }
+}
+
+/// Determine the HTTP methods that are allowed by the given actions. This is always `GET`, followed
+/// by the method of every action in order, without duplicates.
+pub(crate) fn allowed_methods<'a, I>(actions: I) -> Vec<Method>
+where
+    I: IntoIterator<Item = &'a Action>,
+{
+    let mut methods = vec![Method::GET];
+
+    for action in actions {
+        let method = action.method();
+        if !methods.contains(&method) {
+            methods.push(method);
+        }
+    }
+
+    methods
+}
+
+/// Serialize an HTTP method as the string of the method name.
+#[allow(clippy::ref_option)] // Required by the signature of `serialize_with`
+fn serialize_method<S>(value: &Option<Method>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(method) => serializer.serialize_str(method.as_str()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize an HTTP method from the string of the method name.
+fn deserialize_method<'de, D>(deserializer: D) -> Result<Option<Method>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|method| Method::from_bytes(method.as_bytes()).map_err(D::Error::custom))
+        .transpose()
 }
 
 #[cfg(test)]
@@ -612,6 +1000,7 @@ mod tests {
     };
 
     #[test]
+    #[allow(clippy::too_many_lines)] // The full example from the Siren specification
     fn test_example() {
         let document = Document::new(json!({
             "orderNumber": 42,
@@ -748,4 +1137,129 @@ mod tests {
                 })
         );
     }
+
+    #[test]
+    fn custom_methods() {
+        let document = Document::new(json!({}))
+            .with_action(Action::new("check", "/orders/42").with_method(Method::HEAD))
+            .with_action(Action::new("purge", "/orders/42").try_with_method("PURGE").unwrap())
+            .with_action(Action::new("search", "/orders"));
+
+        let serialized = serde_json::to_value(&document).unwrap();
+
+        check!(serialized["actions"][0]["method"] == json!("HEAD"));
+        check!(serialized["actions"][1]["method"] == json!("PURGE"));
+        check!(serialized["actions"][2].get("method") == None);
+        check!(document.actions[2].method() == Method::GET);
+    }
+
+    #[test]
+    fn invalid_method() {
+        let action = Action::new("broken", "/orders/42").try_with_method("NOT A METHOD");
+
+        check!(action.is_err());
+    }
+
+    #[test]
+    fn allowed_methods() {
+        let document = Document::new(json!({}))
+            .with_action(Action::new("update", "/orders/42").with_method(HttpMethods::PUT))
+            .with_action(Action::new("delete", "/orders/42").with_method(HttpMethods::DELETE))
+            .with_action(Action::new("replace", "/orders/42").with_method(HttpMethods::PUT))
+            .with_action(Action::new("search", "/orders"));
+
+        check!(document.allowed_methods() == vec![Method::GET, Method::PUT, Method::DELETE]);
+    }
+
+    #[test]
+    fn allowed_methods_for_self_link() {
+        let document = Document::new(json!({}))
+            .with_link(Link::new("/orders/42").with_rel(LinkRelation::SelfLink))
+            .with_action(Action::new("update", "/orders/42").with_method(HttpMethods::PUT))
+            .with_action(Action::new("add-item", "/orders/42/items").with_method(HttpMethods::POST))
+            .with_action(Action::new("delete", "/orders/42").with_method(HttpMethods::DELETE));
+
+        check!(document.self_link().unwrap().href == "/orders/42");
+        check!(document.resource_actions().len() == 2);
+        check!(document.allowed_methods() == vec![Method::GET, Method::PUT, Method::DELETE]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let input = json!({
+            "class": ["order"],
+            "properties": { "orderNumber": 42 },
+            "entities": [
+                {
+                    "rel": ["http://x.io/rels/order-items"],
+                    "href": "http://api.x.io/orders/42/items"
+                },
+                {
+                    "rel": ["http://x.io/rels/customer"],
+                    "properties": { "customerId": "pj123" }
+                }
+            ],
+            "actions": [
+                {
+                    "name": "add-item",
+                    "method": "POST",
+                    "href": "http://api.x.io/orders/42/items",
+                    "fields": [{ "name": "quantity", "type": "number" }]
+                }
+            ],
+            "links": [{ "rel": ["self"], "href": "http://api.x.io/orders/42" }]
+        });
+
+        let document: Document<Value> = serde_json::from_value(input.clone()).unwrap();
+
+        check!(matches!(document.entities[0], Entity::Link(_)));
+        check!(matches!(document.entities[1], Entity::Representation(_)));
+        check!(document.actions[0].method == Some(Method::POST));
+        check!(serde_json::to_value(&document).unwrap() == input);
+    }
+
+    #[test]
+    fn field_options() {
+        let field = Field::new("shipping")
+            .with_type(FieldTypes::Radio)
+            .with_option(FieldOption::new("standard").with_title("Standard").with_selected())
+            .with_option(FieldOption::new("express").with_title("Express"));
+
+        let serialized = serde_json::to_value(&field).unwrap();
+        check!(
+            serialized
+                == json!({
+                    "name": "shipping",
+                    "type": "radio",
+                    "value": [
+                        { "value": "standard", "title": "Standard", "selected": true },
+                        { "value": "express", "title": "Express" }
+                    ]
+                })
+        );
+
+        let parsed: Field = serde_json::from_value(serialized).unwrap();
+        let options = parsed.options();
+        check!(options.len() == 2);
+        check!(options[0].value == json!("standard"));
+        check!(options[0].selected);
+        check!(!options[1].selected);
+
+        check!(Field::new("tags").with_value(["gift", "urgent"]).options().is_empty());
+        check!(
+            Field::new("shipping")
+                .with_option(FieldOption::new("standard"))
+                .with_value("express")
+                .options()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Field `shipping` already has a value, which options would replace")]
+    fn field_options_with_value() {
+        let _ = Field::new("shipping")
+            .with_value("express")
+            .with_option(FieldOption::new("standard"));
+    }
 }
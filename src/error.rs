@@ -0,0 +1,246 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use http::StatusCode;
+use serde::Serialize;
+
+use crate::{Action, Document, Link, Response};
+
+/// Representation of a Siren document describing an error.
+///
+/// The document has a class of `error`, and properties following the fields of an
+/// [RFC 7807 Problem Details](https://www.rfc-editor.org/rfc/rfc7807) document, so that clients
+/// that understand Problem Details can make sense of it.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ErrorDocument {
+    pub document: Document<ErrorProperties>,
+}
+
+/// The properties of an error document, following the fields of RFC 7807.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorProperties {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title:        String,
+    pub status:       u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail:       Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance:     Option<String>,
+}
+
+impl ErrorDocument {
+    /// Create a new error document.
+    ///
+    /// The problem type defaults to `about:blank` and the title to the canonical reason phrase of
+    /// the status code, as recommended by RFC 7807.
+    ///
+    /// # Parameters
+    /// - `status_code` - The HTTP status code of the error.
+    pub fn new(status_code: StatusCode) -> Self {
+        let title = status_code
+            .canonical_reason()
+            .unwrap_or_else(|| status_code.as_str())
+            .to_owned();
+
+        Self {
+            document: Document::new(ErrorProperties {
+                problem_type: "about:blank".to_owned(),
+                title:        title.clone(),
+                status:       status_code.as_u16(),
+                detail:       None,
+                instance:     None,
+            })
+            .with_class("error")
+            .with_title(title),
+        }
+    }
+
+    /// Apply a lambda to specify additional details on the error.
+    ///
+    /// # Parameters
+    /// - `f` - The lambda function to execute on the error.
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: Fn(Self) -> Self,
+    {
+        f(self)
+    }
+
+    /// Get the HTTP status code of the error.
+    #[must_use]
+    pub fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.document.properties.status)
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// Specify the problem type for the error.
+    ///
+    /// # Parameters
+    /// - `value` - The URI identifying the problem type.
+    pub fn with_type<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.document.properties.problem_type = value.to_string();
+
+        self
+    }
+
+    /// Specify a title for the error. This is used as both the title of the document and the
+    /// `title` property.
+    ///
+    /// # Parameters
+    /// - `value` - The title to specify.
+    pub fn with_title<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.document.properties.title = value.to_string();
+        self.document.title = Some(value.to_string());
+
+        self
+    }
+
+    /// Specify a detailed, human-readable explanation of this occurrence of the error.
+    ///
+    /// # Parameters
+    /// - `value` - The detail to specify.
+    pub fn with_detail<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.document.properties.detail = Some(value.to_string());
+
+        self
+    }
+
+    /// Specify a URI identifying this occurrence of the error.
+    ///
+    /// # Parameters
+    /// - `value` - The instance URI to specify.
+    pub fn with_instance<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.document.properties.instance = Some(value.to_string());
+
+        self
+    }
+
+    /// Specify an additional class for the error.
+    ///
+    /// # Parameters
+    /// - `value` - The class to specify.
+    pub fn with_class<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.document = self.document.with_class(value);
+
+        self
+    }
+
+    /// Specify a link for the error.
+    ///
+    /// # Parameters
+    /// - `value` - The link to specify.
+    pub fn with_link<L>(mut self, value: L) -> Self
+    where
+        L: Into<Link>,
+    {
+        self.document = self.document.with_link(value);
+
+        self
+    }
+
+    /// Specify an action for the error, such as the actions that would have been valid instead.
+    ///
+    /// # Parameters
+    /// - `value` - The action to specify.
+    pub fn with_action<A>(mut self, value: A) -> Self
+    where
+        A: Into<Action>,
+    {
+        self.document = self.document.with_action(value);
+
+        self
+    }
+}
+
+impl From<ErrorDocument> for Document<ErrorProperties> {
+    fn from(error: ErrorDocument) -> Self {
+        error.document
+    }
+}
+
+impl From<ErrorDocument> for Response<ErrorProperties> {
+    fn from(error: ErrorDocument) -> Self {
+        let status_code = error.status_code();
+
+        Response::new(error.document).with_status_code(status_code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+
+    use super::*;
+    use crate::values::LinkRelation;
+
+    #[test]
+    fn defaults() {
+        let error = ErrorDocument::new(StatusCode::NOT_FOUND);
+
+        check!(error.status_code() == StatusCode::NOT_FOUND);
+        check!(
+            serde_json::to_value(&error.document).unwrap()
+                == json!({
+                    "class": ["error"],
+                    "properties": {
+                        "type": "about:blank",
+                        "title": "Not Found",
+                        "status": 404
+                    },
+                    "title": "Not Found"
+                })
+        );
+    }
+
+    #[test]
+    fn full_details() {
+        let error = ErrorDocument::new(StatusCode::FORBIDDEN)
+            .with_type("https://example.com/probs/out-of-credit")
+            .with_title("You do not have enough credit.")
+            .with_detail("Your current balance is 30, but that costs 50.")
+            .with_instance("/account/12345/msgs/abc")
+            .with_class("credit")
+            .with_link(Link::new("/account/12345").with_rel(LinkRelation::Related));
+
+        let response: Response<ErrorProperties> = error.into();
+
+        check!(response.status_code == StatusCode::FORBIDDEN);
+        check!(
+            serde_json::to_value(&response.document).unwrap()
+                == json!({
+                    "class": ["error", "credit"],
+                    "properties": {
+                        "type": "https://example.com/probs/out-of-credit",
+                        "title": "You do not have enough credit.",
+                        "status": 403,
+                        "detail": "Your current balance is 30, but that costs 50.",
+                        "instance": "/account/12345/msgs/abc"
+                    },
+                    "links": [
+                        {
+                            "rel": ["related"],
+                            "href": "/account/12345"
+                        }
+                    ],
+                    "title": "You do not have enough credit."
+                })
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::Write;
+
+use http::{header::ETAG, HeaderValue};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{Document, Response};
+
+impl<T> Document<T>
+where
+    T: Serialize,
+{
+    /// Generate a strong entity tag for this document, for use in an `ETag` header.
+    ///
+    /// The tag is a hash of the canonical serialization of the document from
+    /// [`Document::to_canonical_vec`]. This means that two documents with the same content always
+    /// have the same tag, regardless of the order in which their properties were built.
+    ///
+    /// # Panics
+    /// If the document can not be serialized to JSON.
+    #[must_use]
+    pub fn etag(&self) -> String {
+        let serialized = self.to_canonical_vec().expect("Failed to serialize document");
+
+        entity_tag(&serialized)
+    }
+}
+
+impl<T> Response<T>
+where
+    T: Serialize,
+{
+    /// Include a strong `ETag` header in the response, generated from the document by
+    /// [`Document::etag`].
+    #[must_use]
+    pub fn with_etag(mut self) -> Self {
+        if let Ok(value) = HeaderValue::from_str(&self.document.etag()) {
+            self.headers.insert(ETAG, value);
+        }
+
+        self
+    }
+}
+
+/// Build a strong entity tag, including the quotes, from a hash of the given bytes.
+fn entity_tag(bytes: &[u8]) -> String {
+    let hash = Sha256::digest(bytes);
+
+    let mut result = String::with_capacity(hash.len() * 2 + 2);
+    result.push('"');
+    for byte in hash {
+        // Writing to a `String` can not fail.
+        let _ = write!(result, "{byte:02x}");
+    }
+    result.push('"');
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn stable_etag() {
+        let first = Document::new(json!({"orderNumber": 42, "status": "pending"}));
+        let second = Document::new(json!({"status": "pending", "orderNumber": 42}));
+        let third = Document::new(json!({"orderNumber": 42, "status": "shipped"}));
+
+        check!(first.etag() == second.etag());
+        check!(first.etag() != third.etag());
+        check!(first.etag().len() == 66);
+        check!(first.etag().starts_with('"'));
+    }
+
+    #[test]
+    fn response_etag() {
+        let document = Document::new(json!({"orderNumber": 42}));
+        let etag = document.etag();
+
+        let response = Response::new(document).with_etag();
+
+        check!(response.headers.get(ETAG).unwrap() == etag.as_str());
+    }
+}
@@ -0,0 +1,216 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{Action, Document, EmbeddedRepresentation, Field, Link};
+
+/// Generate the `with_extension` builder method for a type with an `extensions` member.
+///
+/// # Parameters
+/// - `$noun` - What the type is called in the documentation of the method.
+/// - `$members` - The names of the standard members of the type, which extensions may not use.
+macro_rules! with_extension {
+    ($noun:literal, $members:expr) => {
+        #[doc = concat!(
+            "Specify an additional member for the ",
+            $noun,
+            " that is not part of the Siren specification, such as a vendor extension. This is \
+             serialized alongside the standard members, so must not have the same name as one of \
+             them."
+        )]
+        ///
+        /// # Parameters
+        /// - `name` - The name of the member.
+        /// - `value` - The value of the member.
+        ///
+        /// # Panics
+        /// If the name is that of a standard member, or if the value can not be serialized to JSON.
+        pub fn with_extension<N, V>(mut self, name: N, value: V) -> Self
+        where
+            N: ToString,
+            V: Serialize,
+        {
+            insert_extension(&mut self.extensions, $noun, $members, name.to_string(), value);
+
+            self
+        }
+    };
+}
+
+/// The standard members of a document.
+const DOCUMENT_MEMBERS: &[&str] = &[
+    "class",
+    "properties",
+    "entities",
+    "links",
+    "actions",
+    "title",
+];
+
+/// The standard members of an embedded representation.
+const REPRESENTATION_MEMBERS: &[&str] = &[
+    "class",
+    "rel",
+    "properties",
+    "entities",
+    "links",
+    "actions",
+    "title",
+];
+
+/// The standard members of a link.
+const LINK_MEMBERS: &[&str] = &["rel", "class", "href", "title", "type"];
+
+/// The standard members of an action.
+const ACTION_MEMBERS: &[&str] = &["name", "class", "method", "href", "title", "type", "fields"];
+
+/// The standard members of a field, including the constraints on its value.
+const FIELD_MEMBERS: &[&str] = &[
+    "name",
+    "class",
+    "type",
+    "value",
+    "title",
+    "required",
+    "min",
+    "max",
+    "step",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "placeholder",
+    "multiple",
+];
+
+/// Add an extension member, which must not have the name of one of the standard members.
+fn insert_extension<V>(
+    extensions: &mut Map<String, Value>,
+    noun: &str,
+    members: &[&str],
+    name: String,
+    value: V,
+) where
+    V: Serialize,
+{
+    assert!(
+        !members.contains(&name.as_str()),
+        "`{name}` is a standard member of a {noun}, not an extension"
+    );
+
+    let serialized = serde_json::to_value(value).expect("Failed to serialize extension");
+    extensions.insert(name, serialized);
+}
+
+impl<T> Document<T>
+where
+    T: Serialize,
+{
+    with_extension!("document", DOCUMENT_MEMBERS);
+}
+
+impl EmbeddedRepresentation {
+    with_extension!("representation", REPRESENTATION_MEMBERS);
+}
+
+impl Link {
+    with_extension!("link", LINK_MEMBERS);
+}
+
+impl Action {
+    with_extension!("action", ACTION_MEMBERS);
+}
+
+impl Field {
+    with_extension!("field", FIELD_MEMBERS);
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::Entity;
+
+    #[test]
+    fn serialize_extensions() {
+        let document = Document::new(json!({"orderNumber": 42}))
+            .with_extension("lang", "en")
+            .with_embedded_representation(
+                EmbeddedRepresentation::new(json!({})).with_extension("deprecated", true),
+            )
+            .with_link(
+                Link::new("/orders/{id}")
+                    .with_rel("search")
+                    .with_extension("templated", true),
+            )
+            .with_action(
+                Action::new("add-item", "/orders/42/items")
+                    .with_extension("x-rate-limit", 10)
+                    .with_field(Field::new("notes").with_extension("x-widget", "textarea")),
+            );
+
+        check!(
+            serde_json::to_value(&document).unwrap()
+                == json!({
+                    "properties": {"orderNumber": 42},
+                    "entities": [{"properties": {}, "deprecated": true}],
+                    "links": [{"rel": ["search"], "href": "/orders/{id}", "templated": true}],
+                    "actions": [{
+                        "name": "add-item",
+                        "href": "/orders/42/items",
+                        "fields": [{"name": "notes", "x-widget": "textarea"}],
+                        "x-rate-limit": 10
+                    }],
+                    "lang": "en"
+                })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let input = json!({
+            "properties": {},
+            "entities": [
+                {"rel": ["item"], "href": "/items/1", "hreflang": "en"},
+                {"rel": ["customer"], "properties": {}, "x-cached": false}
+            ],
+            "links": [{"rel": ["self"], "href": "/orders/42", "deprecated": true}],
+            "actions": [{
+                "name": "cancel",
+                "href": "/orders/42",
+                "fields": [{"name": "reason", "required": true, "x-widget": "textarea"}],
+                "x-confirm": "Are you sure?"
+            }],
+            "x-version": 3
+        });
+
+        let document: Document<Value> = serde_json::from_value(input.clone()).unwrap();
+
+        check!(document.extensions["x-version"] == json!(3));
+        check!(document.links[0].extensions["deprecated"] == json!(true));
+        check!(document.actions[0].extensions["x-confirm"] == json!("Are you sure?"));
+        check!(document.actions[0].fields[0].required);
+        check!(document.actions[0].fields[0].extensions.len() == 1);
+        let link = match &document.entities[0] {
+            Entity::Link(link) => link,
+            Entity::Representation(_) => panic!("Expected an embedded link"),
+        };
+        check!(link.extensions["hreflang"] == json!("en"));
+
+        check!(serde_json::to_value(&document).unwrap() == input);
+    }
+
+    #[test]
+    #[should_panic(expected = "`href` is a standard member of a link, not an extension")]
+    fn reserved_member() {
+        let _ = Link::new("/orders/42").with_extension("href", "/orders/43");
+    }
+
+    #[test]
+    #[should_panic(expected = "`properties` is a standard member of a document, not an extension")]
+    fn reserved_document_member() {
+        let _ = Document::new(json!({})).with_extension("properties", json!({}));
+    }
+}
@@ -47,10 +47,69 @@
 //!
 //! Currently supported features are:
 //! * `axum` - For the [Axum](https://crates.io/crates/axum) HTTP Server.
+//!
+//! Additional optional features are:
+//! * `cli` - The `siren` command line tool, for validating, outlining and converting documents.
+//! * `crawler` - A crawler that maps out the resources of an API and the links between them. See
+//!   [`Crawler`].
+//! * `openapi` - Documentation of handlers returning Siren responses with
+//!   [utoipa](https://crates.io/crates/utoipa). See the [`openapi`] module.
+//! * `patterns` - Checking submitted values against the `pattern` of their fields when validating
+//!   submissions to actions. Without it, values submitted for fields with a pattern are rejected.
+//! * `resolvers` - Asynchronous resolution of embedded links into full representations. See
+//!   [`EmbeddedResolvers`].
+//! * `schema` - JSON Schemas for Siren documents and for the payloads of actions, using
+//!   [schemars](https://crates.io/crates/schemars).
+//! * `signing` - Detached HMAC-SHA256 and Ed25519 signatures for documents. See the [`signing`]
+//!   module.
+//! * `testing` - Helpers for asserting on Siren documents and responses in tests. See the
+//!   [`testing`] module.
 
+#[cfg(feature = "axum")]
+pub mod axum;
+mod authorization;
+mod canonical;
+#[cfg(feature = "crawler")]
+mod crawl;
 mod document;
+mod error;
+mod etag;
+mod extensions;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+mod pagination;
+mod prefer;
+mod profile;
+#[cfg(feature = "resolvers")]
+mod resolve;
 mod response;
+#[cfg(feature = "schema")]
+mod schema;
+mod search;
+#[cfg(feature = "signing")]
+pub mod signing;
+mod sparse;
+mod state_machine;
+mod template;
+#[cfg(any(feature = "axum", feature = "crawler"))]
+mod uri;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod validation;
 pub mod values;
+pub mod visit;
 
+pub use authorization::Policy;
+#[cfg(feature = "crawler")]
+pub use crawl::*;
 pub use document::*;
+pub use error::*;
+pub use pagination::*;
+pub use prefer::*;
+pub use profile::*;
+#[cfg(feature = "resolvers")]
+pub use resolve::*;
 pub use response::*;
+pub use sparse::*;
+pub use state_machine::*;
+pub use validation::*;
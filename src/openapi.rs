@@ -0,0 +1,168 @@
+//! Integration with [utoipa](https://crates.io/crates/utoipa) for documenting APIs that return
+//! Siren responses.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use utoipa::{
+    openapi::{
+        response::{Response as OpenApiResponse, ResponseBuilder},
+        ContentBuilder, OpenApi, RefOr, Schema,
+    },
+    IntoResponses, Modify, PartialSchema, ToSchema,
+};
+
+use crate::{Action, Document, EmbeddedRepresentation, Entity, Field, Link, Response};
+
+/// The media type that Siren documents are served with.
+const SIREN_MEDIA_TYPE: &str = "application/vnd.siren+json";
+
+/// Documents a handler returning `Response<T>` as a `200 OK` response with a media type of
+/// `application/vnd.siren+json`, whose body is the Siren envelope wrapping the schema of `T`.
+///
+/// The schema of the envelope refers to the schemas of `T` and of the parts of a Siren document,
+/// which need registering as components. See [`SirenComponents`].
+impl<T> IntoResponses for Response<T>
+where
+    T: Serialize + ToSchema,
+{
+    fn responses() -> BTreeMap<String, RefOr<OpenApiResponse>> {
+        let response = ResponseBuilder::new()
+            .description("Siren document")
+            .content(
+                SIREN_MEDIA_TYPE,
+                ContentBuilder::new()
+                    .schema(Some(Document::<T>::schema()))
+                    .build(),
+            )
+            .build();
+
+        [("200".to_owned(), response.into())].into()
+    }
+}
+
+/// Modifier that registers the schemas for the parts of a Siren document - actions, fields,
+/// links and embedded entities - as components of an `OpenAPI` document.
+///
+/// ```rust
+/// # use http_siren::{openapi::SirenComponents, Document, Link, Response};
+/// # use serde::Serialize;
+/// # use utoipa::{OpenApi, ToSchema};
+/// #[derive(Serialize, ToSchema)]
+/// struct Order {
+///     order_number: u32,
+/// }
+///
+/// #[utoipa::path(get, path = "/orders/{id}", responses(Response<Order>))]
+/// async fn get_order() -> Response<Order> {
+///     Document::new(Order { order_number: 42 })
+///         .with_link(Link::new("/orders/42").with_rel("self"))
+///         .into()
+/// }
+///
+/// #[derive(OpenApi)]
+/// #[openapi(paths(get_order), components(schemas(Order)), modifiers(&SirenComponents))]
+/// struct ApiDoc;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SirenComponents;
+
+impl Modify for SirenComponents {
+    fn modify(&self, openapi: &mut OpenApi) {
+        let mut schemas: Vec<(String, RefOr<Schema>)> = vec![
+            (Action::name().into(), Action::schema()),
+            (EmbeddedRepresentation::name().into(), EmbeddedRepresentation::schema()),
+            (Entity::name().into(), Entity::schema()),
+            (Field::name().into(), Field::schema()),
+            (Link::name().into(), Link::schema()),
+        ];
+        Entity::schemas(&mut schemas);
+        Action::schemas(&mut schemas);
+
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .schemas
+            .extend(schemas);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::{json, Value};
+    use utoipa::OpenApi as _;
+
+    use super::*;
+
+    #[derive(Serialize, ToSchema)]
+    struct Order {
+        order_number: u32,
+    }
+
+    #[utoipa::path(get, path = "/orders/{id}", responses(Response<Order>))]
+    async fn get_order() -> Response<Order> {
+        Document::new(Order { order_number: 42 })
+            .with_link(Link::new("/orders/42").with_rel("self"))
+            .into()
+    }
+
+    #[derive(utoipa::OpenApi)]
+    #[openapi(paths(get_order), components(schemas(Order)), modifiers(&SirenComponents))]
+    struct ApiDoc;
+
+    /// Collect every schema reference within the given value.
+    fn references(value: &Value, found: &mut Vec<String>) {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(reference)) = object.get("$ref") {
+                    found.push(reference.clone());
+                }
+                object.values().for_each(|v| references(v, found));
+            },
+            Value::Array(array) => array.iter().for_each(|v| references(v, found)),
+            _ => {},
+        }
+    }
+
+    #[test]
+    fn siren_response() {
+        let openapi = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let response = &openapi["paths"]["/orders/{id}"]["get"]["responses"]["200"];
+        let schema = &response["content"]["application/vnd.siren+json"]["schema"];
+
+        check!(response["content"].as_object().unwrap().len() == 1);
+        check!(schema["required"] == json!(["properties"]));
+        check!(schema["properties"]["properties"]["$ref"] == json!("#/components/schemas/Order"));
+        check!(
+            schema["properties"]["links"]["items"]["$ref"] == json!("#/components/schemas/Link")
+        );
+    }
+
+    #[tokio::test]
+    async fn response_matches_schema() {
+        let openapi = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let response = &openapi["paths"]["/orders/{id}"]["get"]["responses"]["200"];
+        let schema = &response["content"]["application/vnd.siren+json"]["schema"];
+
+        let serialized = serde_json::to_value(&get_order().await.document).unwrap();
+
+        check!(serialized["properties"] == json!({"order_number": 42}));
+        for key in serialized.as_object().unwrap().keys() {
+            check!(schema["properties"].get(key).is_some(), "{key}");
+        }
+    }
+
+    #[test]
+    fn references_resolve() {
+        let openapi = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let mut found = vec![];
+        references(&openapi, &mut found);
+
+        check!(!found.is_empty());
+        for reference in found {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            check!(openapi["components"]["schemas"].get(name).is_some(), "{reference}");
+        }
+    }
+}
@@ -0,0 +1,456 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{template::expand, values::LinkRelation, Document, EmbeddedRepresentation, Link};
+
+/// Builder for Siren documents representing a single page of a collection.
+///
+/// This generates the `self`, `first`, `previous`, `next` and `last` links for the page from a URI
+/// Template, embeds every item on the page with a link relation of `item`, and includes the number
+/// of items on the page and the total number of items in the properties of the document.
+///
+/// The URI Template supports simple expansion (`{var}`) and form-style query expansion
+/// (`{?var,var}` and `{&var,var}`). The variables that are available depend on the pagination
+/// strategy:
+/// - For offset pagination - `offset`, `limit` and `page`, where `page` is the 1-based page number.
+/// - For cursor pagination - `cursor` and `limit`. The `cursor` variable is undefined for the first
+///   page, so will be omitted from query expansions.
+///
+/// ```rust
+/// # use serde_json::json;
+/// # use http_siren::{Document, EmbeddedRepresentation, PageProperties, Paginated};
+/// let document: Document<PageProperties<_>> = Paginated::offset("/orders{?offset,limit}", 20, 10)
+///     .with_total(42)
+///     .with_item(EmbeddedRepresentation::new(json!({"orderNumber": 21})))
+///     .into_document(json!({}));
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Paginated {
+    template: String,
+    strategy: Pagination,
+    total:    Option<u64>,
+    items:    Vec<EmbeddedRepresentation>,
+}
+
+/// The strategy used to paginate a collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pagination {
+    /// Pages are identified by the offset of the first item on the page.
+    Offset { offset: u64, limit: u64 },
+    /// Pages are identified by opaque cursors, with the server providing the cursors of the
+    /// neighbouring pages.
+    Cursor {
+        limit:    u64,
+        current:  Option<String>,
+        next:     Option<String>,
+        previous: Option<String>,
+    },
+}
+
+/// The properties of a paginated document.
+#[derive(Debug, Clone, Serialize)]
+pub struct PageProperties<T>
+where
+    T: Serialize,
+{
+    /// The number of items on this page.
+    pub count:      usize,
+    /// The total number of items in the collection, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total:      Option<u64>,
+    /// Any additional properties of the collection.
+    #[serde(flatten)]
+    pub properties: T,
+}
+
+impl Paginated {
+    /// Create a new builder for offset pagination.
+    ///
+    /// # Parameters
+    /// - `template` - The URI Template for pages of the collection.
+    /// - `offset` - The offset of the first item on this page.
+    /// - `limit` - The maximum number of items on each page.
+    pub fn offset<S>(template: S, offset: u64, limit: u64) -> Self
+    where
+        S: ToString,
+    {
+        Self::new(template, Pagination::Offset { offset, limit })
+    }
+
+    /// Create a new builder for offset pagination, using page numbers instead of offsets.
+    ///
+    /// # Parameters
+    /// - `template` - The URI Template for pages of the collection.
+    /// - `page` - The 1-based page number of this page.
+    /// - `page_size` - The maximum number of items on each page.
+    pub fn page<S>(template: S, page: u64, page_size: u64) -> Self
+    where
+        S: ToString,
+    {
+        Self::offset(
+            template,
+            page.saturating_sub(1).saturating_mul(page_size),
+            page_size,
+        )
+    }
+
+    /// Create a new builder for cursor pagination.
+    ///
+    /// # Parameters
+    /// - `template` - The URI Template for pages of the collection.
+    /// - `limit` - The maximum number of items on each page.
+    pub fn cursor<S>(template: S, limit: u64) -> Self
+    where
+        S: ToString,
+    {
+        Self::new(
+            template,
+            Pagination::Cursor {
+                limit,
+                current:  None,
+                next:     None,
+                previous: None,
+            },
+        )
+    }
+
+    /// Create a new builder with the given pagination strategy.
+    ///
+    /// # Parameters
+    /// - `template` - The URI Template for pages of the collection.
+    /// - `strategy` - The pagination strategy to use.
+    pub fn new<S>(template: S, strategy: Pagination) -> Self
+    where
+        S: ToString,
+    {
+        Self {
+            template: template.to_string(),
+            strategy,
+            total:    None,
+            items:    vec![],
+        }
+    }
+
+    /// Specify the total number of items in the collection.
+    ///
+    /// # Parameters
+    /// - `total` - The total number of items.
+    pub fn with_total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+
+        self
+    }
+
+    /// Specify the cursor of this page. Only applies to cursor pagination.
+    ///
+    /// # Parameters
+    /// - `value` - The cursor of this page.
+    pub fn with_cursor<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        if let Pagination::Cursor { current, .. } = &mut self.strategy {
+            *current = Some(value.to_string());
+        }
+
+        self
+    }
+
+    /// Specify the cursor of the next page. Only applies to cursor pagination.
+    ///
+    /// # Parameters
+    /// - `value` - The cursor of the next page.
+    pub fn with_next_cursor<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        if let Pagination::Cursor { next, .. } = &mut self.strategy {
+            *next = Some(value.to_string());
+        }
+
+        self
+    }
+
+    /// Specify the cursor of the previous page. Only applies to cursor pagination.
+    ///
+    /// # Parameters
+    /// - `value` - The cursor of the previous page.
+    pub fn with_previous_cursor<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        if let Pagination::Cursor { previous, .. } = &mut self.strategy {
+            *previous = Some(value.to_string());
+        }
+
+        self
+    }
+
+    /// Specify an item on this page. The item will be given a link relation of `item` if it does
+    /// not already have one.
+    ///
+    /// # Parameters
+    /// - `value` - The item to specify.
+    pub fn with_item<V>(mut self, value: V) -> Self
+    where
+        V: Into<EmbeddedRepresentation>,
+    {
+        let mut item = value.into();
+
+        let rel = LinkRelation::Item.to_string();
+        if !item.rel.contains(&rel) {
+            item.rel.push(rel);
+        }
+
+        self.items.push(item);
+
+        self
+    }
+
+    /// Specify every item on this page.
+    ///
+    /// # Parameters
+    /// - `values` - The items to specify.
+    pub fn with_items<I>(self, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<EmbeddedRepresentation>,
+    {
+        values.into_iter().fold(self, Self::with_item)
+    }
+
+    /// Build the Siren document for this page.
+    ///
+    /// # Parameters
+    /// - `properties` - Any additional properties for the document.
+    pub fn into_document<T>(self, properties: T) -> Document<PageProperties<T>>
+    where
+        T: Serialize,
+    {
+        let mut document = Document::new(PageProperties {
+            count: self.items.len(),
+            total: self.total,
+            properties,
+        });
+
+        for (rel, href) in self.links() {
+            document = document.with_link(Link::new(href).with_rel(rel));
+        }
+
+        for item in self.items {
+            document = document.with_embedded_representation(item);
+        }
+
+        document
+    }
+
+    /// Generate the links to this page and the other pages of the collection.
+    fn links(&self) -> Vec<(LinkRelation, String)> {
+        let mut links = vec![];
+
+        match &self.strategy {
+            Pagination::Offset { offset, limit } => {
+                let offset = *offset;
+                let limit = (*limit).max(1);
+                let count = self.items.len() as u64;
+
+                let href = |offset: u64| {
+                    expand(
+                        &self.template,
+                        &[
+                            ("offset", Some(offset.to_string())),
+                            ("limit", Some(limit.to_string())),
+                            ("page", Some((offset / limit).saturating_add(1).to_string())),
+                        ],
+                    )
+                };
+
+                links.push((LinkRelation::SelfLink, href(offset)));
+                links.push((LinkRelation::First, href(0)));
+
+                if offset > 0 {
+                    links.push((LinkRelation::Previous, href(offset.saturating_sub(limit))));
+                }
+
+                // There is no next page if its offset can not be represented.
+                let next = offset.checked_add(limit).filter(|next| match self.total {
+                    Some(total) => *next < total,
+                    None => count >= limit,
+                });
+                if let Some(next) = next {
+                    links.push((LinkRelation::Next, href(next)));
+                }
+
+                if let Some(total) = self.total {
+                    let last = total.saturating_sub(1) / limit * limit;
+                    links.push((LinkRelation::Last, href(last)));
+                }
+            },
+            Pagination::Cursor {
+                limit,
+                current,
+                next,
+                previous,
+            } => {
+                let href = |cursor: Option<&String>| {
+                    expand(
+                        &self.template,
+                        &[
+                            ("cursor", cursor.cloned()),
+                            ("limit", Some(limit.to_string())),
+                        ],
+                    )
+                };
+
+                links.push((LinkRelation::SelfLink, href(current.as_ref())));
+                links.push((LinkRelation::First, href(None)));
+
+                if previous.is_some() {
+                    links.push((LinkRelation::Previous, href(previous.as_ref())));
+                }
+                if next.is_some() {
+                    links.push((LinkRelation::Next, href(next.as_ref())));
+                }
+            },
+        }
+
+        links
+    }
+}
+
+impl From<Paginated> for Document<PageProperties<Map<String, Value>>> {
+    fn from(paginated: Paginated) -> Self {
+        paginated.into_document(Map::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+
+    use super::*;
+
+    fn links<T>(document: &Document<T>) -> Vec<(String, String)>
+    where
+        T: Serialize,
+    {
+        document
+            .links
+            .iter()
+            .map(|l| (l.rel[0].clone(), l.href.clone()))
+            .collect()
+    }
+
+    fn items(count: u64) -> Vec<EmbeddedRepresentation> {
+        (0..count)
+            .map(|i| EmbeddedRepresentation::new(json!({ "id": i })))
+            .collect()
+    }
+
+    #[test]
+    fn offset_middle_page() {
+        let document: Document<_> = Paginated::offset("/orders{?offset,limit}", 20, 10)
+            .with_total(42)
+            .with_items(items(10))
+            .into();
+
+        check!(
+            links(&document)
+                == vec![
+                    ("self".to_owned(), "/orders?offset=20&limit=10".to_owned()),
+                    ("first".to_owned(), "/orders?offset=0&limit=10".to_owned()),
+                    ("previous".to_owned(), "/orders?offset=10&limit=10".to_owned()),
+                    ("next".to_owned(), "/orders?offset=30&limit=10".to_owned()),
+                    ("last".to_owned(), "/orders?offset=40&limit=10".to_owned()),
+                ]
+        );
+        check!(document.entities.len() == 10);
+
+        let serialized = serde_json::to_value(&document).unwrap();
+        check!(serialized["properties"] == json!({"count": 10, "total": 42}));
+        check!(serialized["entities"][0]["rel"] == json!(["item"]));
+    }
+
+    #[test]
+    fn offset_last_page() {
+        let document: Document<_> = Paginated::page("/orders?page={page}", 5, 10)
+            .with_total(42)
+            .with_items(items(2))
+            .into();
+
+        check!(
+            links(&document)
+                == vec![
+                    ("self".to_owned(), "/orders?page=5".to_owned()),
+                    ("first".to_owned(), "/orders?page=1".to_owned()),
+                    ("previous".to_owned(), "/orders?page=4".to_owned()),
+                    ("last".to_owned(), "/orders?page=5".to_owned()),
+                ]
+        );
+    }
+
+    #[test]
+    fn offset_unknown_total() {
+        let document: Document<_> = Paginated::offset("/orders{?offset,limit}", 0, 10)
+            .with_items(items(10))
+            .into();
+
+        check!(
+            links(&document)
+                == vec![
+                    ("self".to_owned(), "/orders?offset=0&limit=10".to_owned()),
+                    ("first".to_owned(), "/orders?offset=0&limit=10".to_owned()),
+                    ("next".to_owned(), "/orders?offset=10&limit=10".to_owned()),
+                ]
+        );
+
+        let serialized = serde_json::to_value(&document).unwrap();
+        check!(serialized["properties"] == json!({"count": 10}));
+    }
+
+    #[test]
+    fn offset_overflow() {
+        let document: Document<_> = Paginated::page("/orders{?offset,limit}", u64::MAX, 10)
+            .with_items(items(10))
+            .into();
+        let rels: Vec<_> = links(&document).into_iter().map(|(rel, _)| rel).collect();
+
+        check!(rels == vec!["self", "first", "previous"]);
+        check!(links(&document)[0].1 == format!("/orders?offset={}&limit=10", u64::MAX));
+
+        let document: Document<_> = Paginated::offset("/orders?page={page}", u64::MAX, 1)
+            .with_total(u64::MAX)
+            .into();
+        let rels: Vec<_> = links(&document).into_iter().map(|(rel, _)| rel).collect();
+
+        check!(rels == vec!["self", "first", "previous", "last"]);
+        check!(links(&document)[0].1 == format!("/orders?page={}", u64::MAX));
+    }
+
+    #[test]
+    fn cursor() {
+        let document = Paginated::cursor("/orders{?cursor,limit}", 10)
+            .with_cursor("abc")
+            .with_next_cursor("def")
+            .with_previous_cursor("xyz")
+            .with_items(items(10))
+            .into_document(json!({"status": "pending"}));
+
+        check!(
+            links(&document)
+                == vec![
+                    ("self".to_owned(), "/orders?cursor=abc&limit=10".to_owned()),
+                    ("first".to_owned(), "/orders?limit=10".to_owned()),
+                    ("previous".to_owned(), "/orders?cursor=xyz&limit=10".to_owned()),
+                    ("next".to_owned(), "/orders?cursor=def&limit=10".to_owned()),
+                ]
+        );
+
+        let serialized = serde_json::to_value(&document).unwrap();
+        check!(serialized["properties"] == json!({"count": 10, "status": "pending"}));
+    }
+}
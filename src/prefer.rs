@@ -0,0 +1,123 @@
+use http::HeaderMap;
+use serde::Serialize;
+
+use crate::Document;
+
+/// The preference of a client for the body of a response, as requested using the `return`
+/// preference of the `Prefer` header from [RFC 7240](https://www.rfc-editor.org/rfc/rfc7240).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnPreference {
+    /// The client only needs a minimal response.
+    Minimal,
+    /// The client wants the full representation of the resource.
+    Representation,
+}
+
+impl ReturnPreference {
+    /// Determine the return preference from the `Prefer` headers of a request, if there is one.
+    ///
+    /// # Parameters
+    /// - `headers` - The headers of the request.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get_all("Prefer")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .find_map(|preference| {
+                // Any parameters of the preference are irrelevant for `return`.
+                let preference = preference.split(';').next().unwrap_or_default();
+                let (name, value) = preference.split_once('=')?;
+
+                if !name.trim().eq_ignore_ascii_case("return") {
+                    return None;
+                }
+
+                match value.trim().trim_matches('"') {
+                    "minimal" => Some(Self::Minimal),
+                    "representation" => Some(Self::Representation),
+                    _ => None,
+                }
+            })
+    }
+
+    /// The value of the `Preference-Applied` header for this preference.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimal => "return=minimal",
+            Self::Representation => "return=representation",
+        }
+    }
+}
+
+impl<T> Document<T>
+where
+    T: Serialize,
+{
+    /// Reduce this document to a minimal representation, for clients that have requested one.
+    /// This removes all embedded entities and actions, leaving the class, properties, links and
+    /// title.
+    pub fn into_minimal(mut self) -> Self {
+        self.entities.clear();
+        self.actions.clear();
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Action, Link};
+
+    fn headers(values: &[&'static str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append("Prefer", HeaderValue::from_static(value));
+        }
+
+        headers
+    }
+
+    #[test]
+    fn parse_preference() {
+        check!(ReturnPreference::from_headers(&headers(&[])) == None);
+        check!(ReturnPreference::from_headers(&headers(&["respond-async"])) == None);
+        check!(
+            ReturnPreference::from_headers(&headers(&["return=minimal"]))
+                == Some(ReturnPreference::Minimal)
+        );
+        check!(
+            ReturnPreference::from_headers(&headers(&[
+                "respond-async, wait=10",
+                "return=\"representation\"; foo"
+            ])) == Some(ReturnPreference::Representation)
+        );
+        check!(ReturnPreference::from_headers(&headers(&["return=other"])) == None);
+    }
+
+    #[test]
+    fn minimal_document() {
+        let document = Document::new(json!({"orderNumber": 42}))
+            .with_class("order")
+            .with_embedded_link(Link::new("/orders/42/items").with_rel("items"))
+            .with_action(Action::new("add-item", "/orders/42/items"))
+            .with_link(Link::new("/orders/42").with_rel("self"))
+            .into_minimal();
+
+        check!(
+            serde_json::to_value(&document).unwrap()
+                == json!({
+                    "class": ["order"],
+                    "properties": { "orderNumber": 42 },
+                    "links": [{ "rel": ["self"], "href": "/orders/42" }]
+                })
+        );
+    }
+}
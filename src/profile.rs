@@ -0,0 +1,409 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{values::LinkRelation, Action, Document, Link};
+
+/// Description of a type of resource, from which an
+/// [ALPS](https://datatracker.ietf.org/doc/html/draft-amundsen-richardson-foster-alps) profile can
+/// be generated and linked to from every document of that type.
+///
+/// ```rust
+/// # use http::Method;
+/// # use http_siren::{Action, Document, Field, ResourceProfile};
+/// # use serde_json::json;
+/// let profile = ResourceProfile::new("order", "/profiles/order")
+///     .with_class("order")
+///     .with_property("orderNumber")
+///     .with_property("status")
+///     .with_action(
+///         Action::new("add-item", "/orders/{id}/items")
+///             .with_method(Method::POST)
+///             .with_field(Field::new("productCode")),
+///     )
+///     .with_link_rel("customer");
+///
+/// let alps = profile.to_alps();
+/// let document = Document::new(json!({"orderNumber": 42})).with_profile(&profile);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ResourceProfile {
+    pub name:       String,
+    pub href:       String,
+    pub title:      Option<String>,
+    pub class:      Vec<String>,
+    pub properties: Vec<String>,
+    pub actions:    Vec<Action>,
+    pub link_rels:  Vec<String>,
+}
+
+/// An ALPS document, as served at the href of a [`ResourceProfile`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlpsDocument {
+    pub alps: Alps,
+}
+
+/// The body of an ALPS document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alps {
+    pub version:    String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc:        Option<AlpsDoc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub descriptor: Vec<AlpsDescriptor>,
+}
+
+/// Human readable documentation within an ALPS document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlpsDoc {
+    pub value: String,
+}
+
+/// A single descriptor within an ALPS document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlpsDescriptor {
+    pub id:              String,
+    /// The name of the descriptor, when it differs from its identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name:            Option<String>,
+    #[serde(rename = "type")]
+    pub descriptor_type: AlpsType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc:             Option<AlpsDoc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub descriptor:      Vec<AlpsDescriptor>,
+}
+
+/// The types of ALPS descriptors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlpsType {
+    /// A state element, such as a property or a class.
+    Semantic,
+    /// A safe transition, such as a link or a `GET` action.
+    Safe,
+    /// An unsafe transition, such as a `POST` action.
+    Unsafe,
+    /// An idempotent transition, such as a `PUT` or `DELETE` action.
+    Idempotent,
+}
+
+impl ResourceProfile {
+    /// Create a new resource profile.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the type of resource, used as the identifier of its descriptor.
+    /// - `href` - The URL that the ALPS profile is served at.
+    pub fn new<N, H>(name: N, href: H) -> Self
+    where
+        N: ToString,
+        H: ToString,
+    {
+        Self {
+            name:       name.to_string(),
+            href:       href.to_string(),
+            title:      None,
+            class:      vec![],
+            properties: vec![],
+            actions:    vec![],
+            link_rels:  vec![],
+        }
+    }
+
+    /// Specify a title for the profile.
+    ///
+    /// # Parameters
+    /// - `value` - The title to specify.
+    pub fn with_title<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.title = Some(value.to_string());
+
+        self
+    }
+
+    /// Specify a class that documents of this type have. Documents are matched to profiles by
+    /// having every one of the classes of the profile.
+    ///
+    /// # Parameters
+    /// - `value` - The class to specify.
+    pub fn with_class<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.class.push(value.to_string());
+
+        self
+    }
+
+    /// Specify a property that documents of this type have.
+    ///
+    /// # Parameters
+    /// - `value` - The name of the property.
+    pub fn with_property<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.properties.push(value.to_string());
+
+        self
+    }
+
+    /// Specify an action that documents of this type can have.
+    ///
+    /// # Parameters
+    /// - `value` - The action to specify.
+    pub fn with_action<A>(mut self, value: A) -> Self
+    where
+        A: Into<Action>,
+    {
+        self.actions.push(value.into());
+
+        self
+    }
+
+    /// Specify a link relation that documents of this type can have links with.
+    ///
+    /// # Parameters
+    /// - `value` - The link relation to specify.
+    pub fn with_link_rel<S>(mut self, value: S) -> Self
+    where
+        S: ToString,
+    {
+        self.link_rels.push(value.to_string());
+
+        self
+    }
+
+    /// Check if the given document is of the type that this profile describes, by having every
+    /// class of the profile. Profiles without any classes never match.
+    #[must_use]
+    pub fn matches<T>(&self, document: &Document<T>) -> bool
+    where
+        T: Serialize,
+    {
+        !self.class.is_empty() && self.class.iter().all(|c| document.class.contains(c))
+    }
+
+    /// Generate the ALPS document for this profile.
+    ///
+    /// - The resource itself becomes a `semantic` descriptor, containing a `semantic` descriptor
+    ///   for each property.
+    /// - Each class other than the name of the resource becomes a `semantic` descriptor.
+    /// - Each action becomes a `safe`, `idempotent` or `unsafe` descriptor depending on its
+    ///   method, containing a `semantic` descriptor for each field. Identifiers must be unique
+    ///   within the document, so those of fields are prefixed with the name of their action, as in
+    ///   `add-item.quantity`, and the name of the field is given as the name of the descriptor.
+    /// - Each link relation becomes a `safe` descriptor.
+    #[must_use]
+    pub fn to_alps(&self) -> AlpsDocument {
+        let mut descriptor = vec![AlpsDescriptor {
+            id:              self.name.clone(),
+            name:            None,
+            descriptor_type: AlpsType::Semantic,
+            doc:             self.title.clone().map(|value| AlpsDoc { value }),
+            descriptor:      self
+                .properties
+                .iter()
+                .map(|p| AlpsDescriptor::new(p, AlpsType::Semantic))
+                .collect(),
+        }];
+
+        descriptor.extend(
+            self.class
+                .iter()
+                .filter(|c| **c != self.name)
+                .map(|c| AlpsDescriptor::new(c, AlpsType::Semantic)),
+        );
+
+        descriptor.extend(self.actions.iter().map(|action| {
+            let descriptor_type = match action.method() {
+                Method::GET | Method::HEAD => AlpsType::Safe,
+                Method::PUT | Method::DELETE => AlpsType::Idempotent,
+                _ => AlpsType::Unsafe,
+            };
+
+            AlpsDescriptor {
+                id: action.name.clone(),
+                name: None,
+                descriptor_type,
+                doc: action.title.clone().map(|value| AlpsDoc { value }),
+                descriptor: action
+                    .fields
+                    .iter()
+                    .map(|field| AlpsDescriptor {
+                        id:              format!("{}.{}", action.name, field.name),
+                        name:            Some(field.name.clone()),
+                        descriptor_type: AlpsType::Semantic,
+                        doc:             field.title.clone().map(|value| AlpsDoc { value }),
+                        descriptor:      vec![],
+                    })
+                    .collect(),
+            }
+        }));
+
+        descriptor.extend(
+            self.link_rels
+                .iter()
+                .map(|rel| AlpsDescriptor::new(rel, AlpsType::Safe)),
+        );
+
+        AlpsDocument {
+            alps: Alps {
+                version: "1.0".to_owned(),
+                doc: self.title.clone().map(|value| AlpsDoc { value }),
+                descriptor,
+            },
+        }
+    }
+}
+
+impl AlpsDescriptor {
+    /// Create a descriptor with no documentation or nested descriptors.
+    fn new(id: &str, descriptor_type: AlpsType) -> Self {
+        Self {
+            id: id.to_owned(),
+            name: None,
+            descriptor_type,
+            doc: None,
+            descriptor: vec![],
+        }
+    }
+}
+
+impl<T> Document<T>
+where
+    T: Serialize,
+{
+    /// Specify the profile that describes this document, adding a link to it with a relation of
+    /// `profile` unless the document already has one.
+    ///
+    /// # Parameters
+    /// - `profile` - The profile that describes the document.
+    pub fn with_profile(mut self, profile: &ResourceProfile) -> Self {
+        let rel = LinkRelation::Profile.to_string();
+
+        if !self.links.iter().any(|link| link.rel.contains(&rel)) {
+            self.links
+                .push(Link::new(&profile.href).with_rel(LinkRelation::Profile));
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+
+    use super::*;
+    use crate::Field;
+
+    fn profile() -> ResourceProfile {
+        ResourceProfile::new("order", "/profiles/order")
+            .with_title("An order")
+            .with_class("order")
+            .with_class("archivable")
+            .with_property("orderNumber")
+            .with_action(
+                Action::new("add-item", "/orders/42/items")
+                    .with_method(Method::POST)
+                    .with_title("Add Item")
+                    .with_field(Field::new("productCode").with_title("Product Code")),
+            )
+            .with_action(Action::new("cancel", "/orders/42").with_method(Method::DELETE))
+            .with_action(Action::new("search", "/orders"))
+            .with_link_rel("customer")
+    }
+
+    #[test]
+    fn alps() {
+        check!(
+            serde_json::to_value(profile().to_alps()).unwrap()
+                == json!({
+                    "alps": {
+                        "version": "1.0",
+                        "doc": { "value": "An order" },
+                        "descriptor": [
+                            {
+                                "id": "order",
+                                "type": "semantic",
+                                "doc": { "value": "An order" },
+                                "descriptor": [{ "id": "orderNumber", "type": "semantic" }]
+                            },
+                            { "id": "archivable", "type": "semantic" },
+                            {
+                                "id": "add-item",
+                                "type": "unsafe",
+                                "doc": { "value": "Add Item" },
+                                "descriptor": [{
+                                    "id": "add-item.productCode",
+                                    "name": "productCode",
+                                    "type": "semantic",
+                                    "doc": { "value": "Product Code" }
+                                }]
+                            },
+                            { "id": "cancel", "type": "idempotent" },
+                            { "id": "search", "type": "safe" },
+                            { "id": "customer", "type": "safe" }
+                        ]
+                    }
+                })
+        );
+    }
+
+    #[test]
+    fn unique_ids() {
+        let alps = profile()
+            .with_action(
+                Action::new("update-item", "/orders/42/items/1")
+                    .with_method(Method::PUT)
+                    .with_field(Field::new("quantity")),
+            )
+            .with_action(
+                Action::new("split-item", "/orders/42/items/1")
+                    .with_method(Method::POST)
+                    .with_field(Field::new("quantity")),
+            )
+            .to_alps();
+
+        let mut ids = vec![];
+        let mut pending: Vec<&AlpsDescriptor> = alps.alps.descriptor.iter().collect();
+        while let Some(descriptor) = pending.pop() {
+            ids.push(descriptor.id.as_str());
+            pending.extend(&descriptor.descriptor);
+        }
+        let count = ids.len();
+        ids.sort_unstable();
+        ids.dedup();
+        check!(ids.len() == count);
+
+        let update = &alps.alps.descriptor[5].descriptor[0];
+        check!(update.id == "update-item.quantity");
+        check!(update.name.as_deref() == Some("quantity"));
+    }
+
+    #[test]
+    fn profile_link() {
+        let document = Document::new(json!({}))
+            .with_profile(&profile())
+            .with_profile(&profile());
+
+        check!(document.links.len() == 1);
+        check!(document.links[0].href == "/profiles/order");
+        check!(document.links[0].rel == vec!["profile"]);
+    }
+
+    #[test]
+    fn matches() {
+        let profile = profile();
+
+        check!(profile.matches(&Document::new(()).with_class("order").with_class("archivable")));
+        check!(!profile.matches(&Document::new(()).with_class("order")));
+        check!(!ResourceProfile::new("empty", "/profiles/empty").matches(&Document::new(())));
+    }
+}
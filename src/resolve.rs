@@ -0,0 +1,400 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use futures_util::future::join_all;
+use serde::Serialize;
+use tokio::sync::Semaphore;
+
+use crate::{Document, EmbeddedRepresentation, Entity, Link};
+
+/// Boxed future returned by a registered resolver.
+type ResolverFuture = Pin<Box<dyn Future<Output = Option<EmbeddedRepresentation>> + Send>>;
+
+/// Type-erased resolver for a single link relation.
+type BoxedResolver = Arc<dyn Fn(Link) -> ResolverFuture + Send + Sync>;
+
+/// Set of asynchronous resolvers, keyed by link relation, that expand the embedded links in a
+/// document into full representations of the resources they link to. See
+/// [`Document::resolve_embedded`].
+///
+/// ```rust
+/// # use http_siren::{Document, EmbeddedRepresentation, EmbeddedResolvers, Link};
+/// # use serde_json::json;
+/// # use std::{convert::Infallible, time::Duration};
+/// # async fn example() {
+/// let resolvers = EmbeddedResolvers::new()
+///     .with_resolver("customer", |link: Link| async move {
+///         Ok::<_, Infallible>(EmbeddedRepresentation::new(json!({"href": link.href})))
+///     })
+///     .with_concurrency(4)
+///     .with_max_depth(2)
+///     .with_timeout(Duration::from_secs(1));
+///
+/// let document = Document::new(json!({"orderNumber": 42}))
+///     .with_embedded_link(Link::new("/customers/pj123").with_rel("customer"))
+///     .resolve_embedded(&resolvers)
+///     .await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct EmbeddedResolvers {
+    resolvers:   HashMap<String, BoxedResolver>,
+    concurrency: usize,
+    max_depth:   usize,
+    timeout:     Option<Duration>,
+}
+
+impl Default for EmbeddedResolvers {
+    fn default() -> Self {
+        Self {
+            resolvers:   HashMap::new(),
+            concurrency: 8,
+            max_depth:   1,
+            timeout:     None,
+        }
+    }
+}
+
+impl Debug for EmbeddedResolvers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddedResolvers")
+            .field("rels", &self.resolvers.keys().collect::<Vec<_>>())
+            .field("concurrency", &self.concurrency)
+            .field("max_depth", &self.max_depth)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl EmbeddedResolvers {
+    /// Create a new set of resolvers, with none registered. By default, up to 8 resolvers run at
+    /// once, only the embedded links of the document itself are resolved, and there is no timeout.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the resolver for embedded links with the given link relation. If the resolver
+    /// fails then the embedded link is kept as it is.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation that the resolver handles.
+    /// - `resolver` - Async callback to resolve an embedded link into the representation it links
+    ///   to.
+    #[must_use]
+    pub fn with_resolver<R, F, Fut, E>(mut self, rel: R, resolver: F) -> Self
+    where
+        R: ToString,
+        F: Fn(Link) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<EmbeddedRepresentation, E>> + Send + 'static,
+    {
+        let resolver: BoxedResolver = Arc::new(move |link| {
+            let future = resolver(link);
+            Box::pin(async move { future.await.ok() })
+        });
+        self.resolvers.insert(rel.to_string(), resolver);
+
+        self
+    }
+
+    /// Specify the maximum number of resolvers that may run at once.
+    ///
+    /// # Parameters
+    /// - `value` - The concurrency limit. A limit of zero is treated as one.
+    #[must_use]
+    pub fn with_concurrency(mut self, value: usize) -> Self {
+        self.concurrency = value.max(1);
+
+        self
+    }
+
+    /// Specify how deeply to resolve embedded links. A depth of 1 resolves only the embedded links
+    /// of the document itself, 2 also resolves those within the resolved representations, and so
+    /// on. A depth of zero resolves nothing.
+    ///
+    /// # Parameters
+    /// - `value` - The depth limit.
+    #[must_use]
+    pub fn with_max_depth(mut self, value: usize) -> Self {
+        self.max_depth = value;
+
+        self
+    }
+
+    /// Specify how long to wait for each resolver. Embedded links whose resolver takes longer are
+    /// kept as they are.
+    ///
+    /// # Parameters
+    /// - `value` - The timeout.
+    #[must_use]
+    pub fn with_timeout(mut self, value: Duration) -> Self {
+        self.timeout = Some(value);
+
+        self
+    }
+}
+
+impl<T> Document<T>
+where
+    T: Serialize,
+{
+    /// Expand the embedded links in this document into full representations, using the resolver
+    /// registered for the first of their link relations that has one.
+    ///
+    /// Resolvers run concurrently, up to the concurrency limit of the resolvers. Embedded links
+    /// whose resolver fails or times out, or which have no resolver, are kept as embedded links.
+    /// Resolved representations are given the link relations, classes and title of the embedded
+    /// link if they don't have any of their own, and always require the permissions that the
+    /// embedded link requires as well as their own.
+    ///
+    /// # Parameters
+    /// - `resolvers` - The resolvers to use.
+    pub async fn resolve_embedded(mut self, resolvers: &EmbeddedResolvers) -> Self {
+        let resolution = Resolution {
+            resolvers,
+            semaphore: Semaphore::new(resolvers.concurrency),
+        };
+
+        self.entities = resolution
+            .resolve_entities(self.entities, resolvers.max_depth)
+            .await;
+
+        self
+    }
+}
+
+/// State of a single call to [`Document::resolve_embedded`].
+struct Resolution<'a> {
+    resolvers: &'a EmbeddedResolvers,
+    semaphore: Semaphore,
+}
+
+impl<'a> Resolution<'a> {
+    /// Resolve the given embedded entities, and those within them down to the given depth.
+    fn resolve_entities(
+        &'a self,
+        entities: Vec<Entity>,
+        depth: usize,
+    ) -> Pin<Box<dyn Future<Output = Vec<Entity>> + Send + 'a>> {
+        Box::pin(async move {
+            if depth == 0 {
+                return entities;
+            }
+
+            join_all(
+                entities
+                    .into_iter()
+                    .map(|entity| self.resolve_entity(entity, depth)),
+            )
+            .await
+        })
+    }
+
+    /// Resolve a single embedded entity, and those within it down to the given depth.
+    async fn resolve_entity(&'a self, entity: Entity, depth: usize) -> Entity {
+        let mut representation = match entity {
+            Entity::Link(link) => match self.fetch(&link).await {
+                Some(representation) => representation,
+                None => return Entity::Link(link),
+            },
+            Entity::Representation(representation) => representation,
+        };
+
+        representation.entities = self
+            .resolve_entities(representation.entities, depth - 1)
+            .await;
+
+        Entity::Representation(representation)
+    }
+
+    /// Run the resolver for the given embedded link, if there is one.
+    async fn fetch(&self, link: &Link) -> Option<EmbeddedRepresentation> {
+        let resolver = link
+            .rel
+            .iter()
+            .find_map(|rel| self.resolvers.resolvers.get(rel))?;
+
+        let _permit = self.semaphore.acquire().await.ok()?;
+        let future = resolver(link.clone());
+
+        let mut representation = match self.resolvers.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, future).await.ok()??,
+            None => future.await?,
+        };
+
+        if representation.rel.is_empty() {
+            representation.rel.clone_from(&link.rel);
+        }
+        if representation.class.is_empty() {
+            representation.class.clone_from(&link.class);
+        }
+        if representation.title.is_none() {
+            representation.title.clone_from(&link.title);
+        }
+        for permission in &link.requires {
+            if !representation.requires.contains(permission) {
+                representation.requires.push(permission.clone());
+            }
+        }
+
+        Some(representation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use assert2::check;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn document() -> Document<Value> {
+        Document::new(json!({"orderNumber": 42}))
+            .with_embedded_link(Link::new("/customers/pj123").with_rel("customer"))
+            .with_embedded_link(Link::new("/orders/42/items").with_rel("items"))
+            .with_embedded_link(Link::new("/orders/42/invoice").with_rel("invoice"))
+    }
+
+    fn hrefs(entities: &[Entity]) -> Vec<String> {
+        entities
+            .iter()
+            .map(|entity| match entity {
+                Entity::Link(link) => format!("link {}", link.href),
+                Entity::Representation(representation) => {
+                    format!("representation {}", representation.properties["href"])
+                },
+            })
+            .collect()
+    }
+
+    async fn customer(link: Link) -> Result<EmbeddedRepresentation, Infallible> {
+        Ok(EmbeddedRepresentation::new(json!({"href": link.href}))
+            .with_embedded_link(Link::new("/addresses/1").with_rel("address")))
+    }
+
+    #[tokio::test]
+    async fn resolve_with_fallback() {
+        let resolvers = EmbeddedResolvers::new()
+            .with_resolver("customer", customer)
+            .with_resolver("items", |_| async { Err("Items are unavailable") });
+
+        let document = document().resolve_embedded(&resolvers).await;
+
+        check!(
+            hrefs(&document.entities)
+                == vec![
+                    "representation \"/customers/pj123\"",
+                    "link /orders/42/items",
+                    "link /orders/42/invoice",
+                ]
+        );
+        let customer = match &document.entities[0] {
+            Entity::Representation(customer) => customer,
+            Entity::Link(_) => unreachable!(),
+        };
+        check!(customer.rel == vec!["customer"]);
+        check!(hrefs(&customer.entities) == vec!["link /addresses/1"]);
+    }
+
+    #[tokio::test]
+    async fn keep_link_members() {
+        let resolvers = EmbeddedResolvers::new().with_resolver("customer", customer);
+
+        let document = Document::new(json!({}))
+            .with_embedded_link(
+                Link::new("/customers/pj123")
+                    .with_rel("customer")
+                    .with_class("person")
+                    .with_title("Customer")
+                    .requires("customers:read"),
+            )
+            .resolve_embedded(&resolvers)
+            .await;
+
+        let customer = match &document.entities[0] {
+            Entity::Representation(customer) => customer,
+            Entity::Link(_) => unreachable!(),
+        };
+        check!(customer.class == vec!["person"]);
+        check!(customer.title == Some("Customer".to_owned()));
+        check!(customer.requires == vec!["customers:read"]);
+
+        let denied = document.authorize(&|_: &str| false);
+        check!(denied.entities.is_empty());
+    }
+
+    #[tokio::test]
+    async fn depth_limit() {
+        let resolvers = EmbeddedResolvers::new()
+            .with_resolver("customer", customer)
+            .with_resolver("address", |link: Link| async move {
+                Ok::<_, Infallible>(EmbeddedRepresentation::new(json!({"href": link.href})))
+            })
+            .with_max_depth(2);
+
+        let resolved = document().resolve_embedded(&resolvers).await;
+        let customer = match &resolved.entities[0] {
+            Entity::Representation(customer) => customer,
+            Entity::Link(_) => unreachable!(),
+        };
+        check!(hrefs(&customer.entities) == vec!["representation \"/addresses/1\""]);
+
+        let unresolved = document().resolve_embedded(&resolvers.with_max_depth(0)).await;
+        check!(hrefs(&unresolved.entities)[0] == "link /customers/pj123");
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        let resolvers = EmbeddedResolvers::new()
+            .with_resolver("customer", |link: Link| async move {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                customer(link).await
+            })
+            .with_timeout(Duration::from_millis(10));
+
+        let document = document().resolve_embedded(&resolvers).await;
+
+        check!(hrefs(&document.entities)[0] == "link /customers/pj123");
+    }
+
+    #[tokio::test]
+    async fn concurrency_limit() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let resolver = {
+            let running = running.clone();
+            let peak = peak.clone();
+            move |link: Link| {
+                let running = running.clone();
+                let peak = peak.clone();
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+
+                    Ok::<_, Infallible>(EmbeddedRepresentation::new(json!({"href": link.href})))
+                }
+            }
+        };
+
+        let document = (0..6).fold(Document::new(json!({})), |document, i| {
+            document.with_embedded_link(Link::new(format!("/items/{i}")).with_rel("item"))
+        });
+        let resolvers = EmbeddedResolvers::new()
+            .with_resolver("item", resolver)
+            .with_concurrency(2);
+
+        let document = document.resolve_embedded(&resolvers).await;
+
+        check!(document.entities.len() == 6);
+        check!(peak.load(Ordering::SeqCst) == 2);
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "axum")]
 mod axum;
 
-use http::{HeaderMap, StatusCode};
+use http::{header::ALLOW, HeaderMap, HeaderValue, Method, StatusCode};
 use serde::Serialize;
 
 use super::Document;
@@ -65,6 +65,25 @@ where
 
         self
     }
+
+    /// Include an `Allow` header in the response, listing the HTTP methods that the actions on the
+    /// document make available for the resource it represents.
+    #[must_use]
+    pub fn with_allow_header(mut self) -> Self {
+        let allow = self
+            .document
+            .allowed_methods()
+            .iter()
+            .map(Method::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if let Ok(value) = HeaderValue::from_str(&allow) {
+            self.headers.insert(ALLOW, value);
+        }
+
+        self
+    }
 }
 
 impl<T> From<Document<T>> for Response<T>
@@ -2,18 +2,42 @@ use axum::{response::IntoResponse, Json};
 use serde::Serialize;
 
 use super::Response;
+use crate::{
+    authorization::DenyAll,
+    axum::{current_policy, refresh_representation_headers, ResourceActions},
+};
 
 impl<T> IntoResponse for Response<T>
 where
     T: Serialize,
 {
     fn into_response(self) -> axum::response::Response {
-        let body = Json(self.document);
+        let mut extra_headers = self.headers;
+
+        // Anything that requires a permission is withheld unless an `AuthorizationLayer` has
+        // provided the policy of the caller.
+        let document = if self.document.is_restricted() {
+            let document = match current_policy() {
+                Some(policy) => self.document.authorize(&*policy),
+                None => self.document.authorize(&DenyAll),
+            };
+            refresh_representation_headers(&mut extra_headers, &document);
+
+            document
+        } else {
+            self.document
+        };
+
+        let resource = ResourceActions::new(&document);
+
+        let body = Json(document);
         let mut response = (self.status_code, body).into_response();
 
+        response.extensions_mut().insert(resource);
+
         let headers = response.headers_mut();
 
-        for (header_name, header_value) in self.headers {
+        for (header_name, header_value) in extra_headers {
             if let Some(header_name) = header_name {
                 headers.append(header_name, header_value);
             }
@@ -31,6 +55,11 @@ where
 }
 
 #[cfg(test)]
+#[allow(
+    clippy::needless_raw_string_hashes,
+    clippy::semicolon_if_nothing_returned,
+    clippy::too_many_lines
+)] // Inline snapshots are written by `cargo insta` in this form
 mod tests {
     use std::str::FromStr;
 
@@ -300,4 +329,33 @@ mod tests {
         }
         "###)
     }
+
+    #[tokio::test]
+    async fn allow_header() {
+        let document = crate::Document::new(json!({}))
+            .with_action(Action::new("update", "/orders/42").with_method(http::Method::PUT))
+            .with_action(Action::new("delete", "/orders/42").with_method(HttpMethods::DELETE));
+        let response = crate::Response::new(document).with_allow_header();
+
+        let http_response = response.into_response();
+        check!(http_response.status() == StatusCode::OK);
+        check!(http_response.headers().get("Allow").unwrap() == "GET, PUT, DELETE");
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn testing_helpers() {
+        let document = crate::Document::new(json!({"orderNumber": 42}))
+            .with_class("order")
+            .with_link(Link::new("http://api.x.io/orders/42").with_rel(LinkRelation::SelfLink));
+        let response = crate::Response::new(document).with_status_code(StatusCode::ACCEPTED);
+
+        crate::testing::SirenAssertion::from_axum_response(response.into_response())
+            .await
+            .has_status_code(StatusCode::ACCEPTED)
+            .has_header("Content-Type", "application/vnd.siren+json")
+            .has_class("order")
+            .has_property("orderNumber", 42)
+            .has_link(LinkRelation::SelfLink, "http://api.x.io/orders/42");
+    }
 }
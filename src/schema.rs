@@ -0,0 +1,225 @@
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{Action, Document, Field};
+
+impl<T> Document<T>
+where
+    T: Serialize + JsonSchema,
+{
+    /// Generate a JSON Schema for Siren documents whose properties have the type `T`. This
+    /// describes the entire Siren envelope, with the schema of `T` used for the properties.
+    #[must_use]
+    pub fn schema() -> Schema {
+        SchemaGenerator::default().into_root_schema_for::<Self>()
+    }
+}
+
+impl Action {
+    /// Generate a JSON Schema for the payloads that can be submitted to this action, derived
+    /// from its fields.
+    ///
+    /// Each field becomes a property of the schema, with a type derived from the type of the
+    /// field - for example `number` fields become numbers, `checkbox` fields become booleans and
+    /// `email` fields become strings with a format of `email`. The title and current value of the
+    /// field become the title and default value of the property.
+    #[must_use]
+    pub fn request_schema(&self) -> Schema {
+        let properties: Map<String, Value> = self
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), field.schema().to_value()))
+            .collect();
+        let required: Vec<&str> = self
+            .fields
+            .iter()
+            .filter(|field| field.required)
+            .map(|field| field.name.as_str())
+            .collect();
+
+        let mut schema = json_schema!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": properties,
+        });
+
+        if !required.is_empty() {
+            schema.insert("required".to_owned(), Value::from(required));
+        }
+        if let Some(title) = &self.title {
+            schema.insert("title".to_owned(), Value::from(title.clone()));
+        }
+
+        schema
+    }
+}
+
+impl Field {
+    /// Generate the JSON Schema for the values of this field.
+    fn schema(&self) -> Schema {
+        let mut schema = match self.input_type.as_deref() {
+            Some("number" | "range") => json_schema!({ "type": "number" }),
+            Some("checkbox") => json_schema!({ "type": "boolean" }),
+            Some("email") => json_schema!({ "type": "string", "format": "email" }),
+            Some("url") => json_schema!({ "type": "string", "format": "uri" }),
+            Some("date") => json_schema!({ "type": "string", "format": "date" }),
+            Some("datetime" | "datetime-local") => {
+                json_schema!({ "type": "string", "format": "date-time" })
+            },
+            Some("time") => json_schema!({ "type": "string", "format": "time" }),
+            _ => json_schema!({ "type": "string" }),
+        };
+
+        if let Some(title) = &self.title {
+            schema.insert("title".to_owned(), Value::from(title.clone()));
+        }
+        let options = self.options();
+        if options.is_empty() {
+            if let Some(value) = &self.value {
+                schema.insert("default".to_owned(), value.clone());
+            }
+        }
+        if let Some(min) = self.min {
+            schema.insert("minimum".to_owned(), Value::from(min));
+        }
+        if let Some(max) = self.max {
+            schema.insert("maximum".to_owned(), Value::from(max));
+        }
+        if let Some(step) = self.step {
+            schema.insert("multipleOf".to_owned(), Value::from(step));
+        }
+        if let Some(min_length) = self.min_length {
+            schema.insert("minLength".to_owned(), Value::from(min_length));
+        }
+        if let Some(max_length) = self.max_length {
+            schema.insert("maxLength".to_owned(), Value::from(max_length));
+        }
+        if let Some(pattern) = &self.pattern {
+            // JSON Schema patterns are unanchored, but field patterns must match the whole value
+            schema.insert("pattern".to_owned(), Value::from(format!("^(?:{pattern})$")));
+        }
+        if let Some(placeholder) = &self.placeholder {
+            schema.insert("examples".to_owned(), Value::from(vec![placeholder.clone()]));
+        }
+        if !options.is_empty() {
+            let values: Vec<Value> = options.iter().map(|o| o.value.clone()).collect();
+            schema.insert("enum".to_owned(), Value::from(values));
+        }
+
+        let selected: Vec<Value> = options
+            .iter()
+            .filter(|o| o.selected)
+            .map(|o| o.value.clone())
+            .collect();
+
+        if self.multiple {
+            let mut schema = json_schema!({
+                "type": "array",
+                "items": schema,
+            });
+            if !selected.is_empty() {
+                schema.insert("default".to_owned(), Value::from(selected));
+            }
+
+            schema
+        } else {
+            if let Some(value) = selected.into_iter().next() {
+                schema.insert("default".to_owned(), value);
+            }
+
+            schema
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use http::Method;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{values::FieldTypes, FieldOption};
+
+    #[derive(Serialize, JsonSchema)]
+    #[serde(rename_all = "camelCase")]
+    struct OrderProperties {
+        order_number: u32,
+        status:       String,
+    }
+
+    #[test]
+    fn document_schema() {
+        let schema = Document::<OrderProperties>::schema().to_value();
+
+        check!(schema["type"] == json!("object"));
+        check!(schema["required"] == json!(["properties"]));
+        check!(schema["properties"]["class"]["type"] == json!("array"));
+        check!(schema["properties"]["properties"]["$ref"] == json!("#/$defs/OrderProperties"));
+        check!(
+            schema["$defs"]["OrderProperties"]["properties"]["orderNumber"]["type"]
+                == json!("integer")
+        );
+        check!(
+            schema["$defs"]["Action"]["properties"]["method"]["type"] == json!(["string", "null"])
+        );
+        check!(schema["$defs"]["Link"]["required"] == json!(["href"]));
+    }
+
+    #[test]
+    fn action_schema() {
+        let action = Action::new("add-item", "/orders/42/items")
+            .with_method(Method::POST)
+            .with_title("Add Item")
+            .with_field(Field::new("orderNumber").with_type(FieldTypes::Hidden).with_value("42"))
+            .with_field(
+                Field::new("productCode")
+                    .with_type(FieldTypes::Text)
+                    .with_required()
+                    .with_pattern("[A-Z]+-[0-9]+"),
+            )
+            .with_field(
+                Field::new("quantity")
+                    .with_type(FieldTypes::Number)
+                    .with_title("Quantity")
+                    .with_min(1.0)
+                    .with_max(99.0),
+            )
+            .with_field(Field::new("giftWrap").with_type(FieldTypes::Checkbox))
+            .with_field(Field::new("contact").with_type(FieldTypes::Email))
+            .with_field(
+                Field::new("tags")
+                    .with_multiple()
+                    .with_option(FieldOption::new("gift").with_selected())
+                    .with_option(FieldOption::new("urgent")),
+            );
+
+        check!(
+            action.request_schema().to_value()
+                == json!({
+                    "$schema": "https://json-schema.org/draft/2020-12/schema",
+                    "type": "object",
+                    "properties": {
+                        "orderNumber": { "type": "string", "default": "42" },
+                        "productCode": { "type": "string", "pattern": "^(?:[A-Z]+-[0-9]+)$" },
+                        "quantity": {
+                            "type": "number",
+                            "title": "Quantity",
+                            "minimum": 1.0,
+                            "maximum": 99.0
+                        },
+                        "giftWrap": { "type": "boolean" },
+                        "contact": { "type": "string", "format": "email" },
+                        "tags": {
+                            "type": "array",
+                            "items": { "type": "string", "enum": ["gift", "urgent"] },
+                            "default": ["gift"]
+                        }
+                    },
+                    "required": ["productCode"],
+                    "title": "Add Item"
+                })
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{values::FieldTypes, Action, Field};
+
+impl Action {
+    /// Create a new `GET` action that describes the query parameters of a search, with a field
+    /// for every member of the query and the current values of the query pre-filled.
+    ///
+    /// Each member of the query becomes a field with the same name. The type of the field is
+    /// derived from the current value of the member:
+    /// - Numbers become `number` fields.
+    /// - Booleans become `checkbox` fields.
+    /// - Everything else becomes a `search` field.
+    ///
+    /// Members that are `null` produce a field with no value, and members that are arrays or
+    /// objects are skipped, since they can not be represented as query parameters. Members that
+    /// are not serialized at all, for example due to `skip_serializing_if`, will not produce a
+    /// field, so optional members should be serialized as `null` instead.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the action.
+    /// - `href` - The href for the action.
+    /// - `query` - The query to describe, with the values of the current request.
+    ///
+    /// # Panics
+    /// If the query can not be serialized to JSON.
+    pub fn from_query<N, H, Q>(name: N, href: H, query: &Q) -> Self
+    where
+        N: ToString,
+        H: ToString,
+        Q: Serialize,
+    {
+        let serialized = serde_json::to_value(query).expect("Failed to serialize query");
+
+        let mut action = Self::new(name, href).with_method(http::Method::GET);
+
+        if let Value::Object(members) = serialized {
+            for (name, value) in members {
+                let field = match value {
+                    Value::Null => Field::new(name).with_type(FieldTypes::Search),
+                    Value::Number(_) => Field::new(name)
+                        .with_type(FieldTypes::Number)
+                        .with_value(value),
+                    Value::Bool(_) => Field::new(name)
+                        .with_type(FieldTypes::Checkbox)
+                        .with_value(value),
+                    Value::String(_) => Field::new(name)
+                        .with_type(FieldTypes::Search)
+                        .with_value(value),
+                    Value::Array(_) | Value::Object(_) => continue,
+                };
+
+                action = action.with_field(field);
+            }
+        }
+
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct OrderSearch {
+        status:    Option<String>,
+        customer:  Option<String>,
+        sort:      String,
+        page_size: u32,
+        archived:  bool,
+        tags:      Vec<String>,
+    }
+
+    #[test]
+    fn from_query() {
+        let query = OrderSearch {
+            status:    Some("pending".to_owned()),
+            customer:  None,
+            sort:      "-created".to_owned(),
+            page_size: 20,
+            archived:  false,
+            tags:      vec![],
+        };
+
+        let action = Action::from_query("search", "/orders", &query).with_title("Search Orders");
+
+        check!(
+            serde_json::to_value(&action).unwrap()
+                == json!({
+                    "name": "search",
+                    "method": "GET",
+                    "href": "/orders",
+                    "title": "Search Orders",
+                    "fields": [
+                        { "name": "status", "type": "search", "value": "pending" },
+                        { "name": "customer", "type": "search" },
+                        { "name": "sort", "type": "search", "value": "-created" },
+                        { "name": "pageSize", "type": "number", "value": 20 },
+                        { "name": "archived", "type": "checkbox", "value": false }
+                    ]
+                })
+        );
+    }
+}
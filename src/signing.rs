@@ -0,0 +1,512 @@
+//! Detached signatures for Siren documents, so that clients can detect documents that have been
+//! tampered with by intermediaries such as caches.
+//!
+//! Documents are signed over their canonical serialization from
+//! [`Document::to_canonical_vec`](crate::Document::to_canonical_vec), and the signature is sent in
+//! the `Siren-Signature` header of the response:
+//!
+//! ```text
+//! Siren-Signature: keyid="2024-01", alg="hmac-sha256", sig="x8aN2...="
+//! ```
+//!
+//! Both HMAC-SHA256, for when the server and client share a secret, and Ed25519, for when they
+//! don't, are supported.
+//!
+//! ```rust
+//! # use http_siren::{signing::{Signer, Verifier}, Document};
+//! # use serde_json::{json, Value};
+//! let signer = Signer::hmac_sha256("secret").with_key_id("2024-01").unwrap();
+//! let document = Document::new(json!({"orderNumber": 42}));
+//! let signature = signer.sign(&document).unwrap();
+//!
+//! let body = serde_json::to_vec(&document).unwrap();
+//! let verified: Document<Value> = Verifier::hmac_sha256("secret")
+//!     .verify(&signature, &body)
+//!     .unwrap();
+//! ```
+
+#![allow(clippy::needless_pass_by_value)]
+
+use std::fmt::{Debug, Formatter};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use derive_more::Display;
+use ed25519_dalek::{Signer as _, SigningKey, Verifier as _, VerifyingKey};
+use hmac::{Hmac, Mac};
+use http::HeaderValue;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::{Document, Response};
+
+/// The name of the header that carries the signature of a document.
+pub const SIGNATURE_HEADER: &str = "Siren-Signature";
+
+/// The key material for signing documents.
+#[derive(Clone)]
+enum SignerKey {
+    HmacSha256(Vec<u8>),
+    Ed25519(Box<SigningKey>),
+}
+
+/// The key material for verifying signatures.
+#[derive(Clone)]
+enum VerifierKey {
+    HmacSha256(Vec<u8>),
+    Ed25519(Box<VerifyingKey>),
+}
+
+/// Produces detached signatures for Siren documents.
+#[derive(Clone)]
+pub struct Signer {
+    key:    SignerKey,
+    key_id: Option<String>,
+}
+
+impl Signer {
+    /// Create a signer that uses HMAC-SHA256 with the given shared secret.
+    ///
+    /// # Parameters
+    /// - `key` - The shared secret.
+    pub fn hmac_sha256<K>(key: K) -> Self
+    where
+        K: AsRef<[u8]>,
+    {
+        Self {
+            key:    SignerKey::HmacSha256(key.as_ref().to_vec()),
+            key_id: None,
+        }
+    }
+
+    /// Create a signer that uses Ed25519 with the given private key.
+    ///
+    /// # Parameters
+    /// - `key` - The private key.
+    #[must_use]
+    pub fn ed25519(key: SigningKey) -> Self {
+        Self {
+            key:    SignerKey::Ed25519(Box::new(key)),
+            key_id: None,
+        }
+    }
+
+    /// Specify an identifier for the key, which is included in signatures so that clients can
+    /// pick the correct key to verify them with.
+    ///
+    /// # Parameters
+    /// - `key_id` - The identifier of the key.
+    ///
+    /// # Errors
+    /// If the identifier can not be included in a `Siren-Signature` header. It must be made up of
+    /// visible ASCII characters other than `"`, `,` and `\`.
+    pub fn with_key_id<S>(mut self, key_id: S) -> Result<Self, InvalidKeyId>
+    where
+        S: ToString,
+    {
+        let key_id = key_id.to_string();
+        let valid = !key_id.is_empty()
+            && key_id
+                .chars()
+                .all(|c| c.is_ascii_graphic() && !matches!(c, '"' | ',' | '\\'));
+        if !valid {
+            return Err(InvalidKeyId(key_id));
+        }
+
+        self.key_id = Some(key_id);
+
+        Ok(self)
+    }
+
+    /// Sign the given document, producing the value for the `Siren-Signature` header.
+    ///
+    /// # Parameters
+    /// - `document` - The document to sign.
+    ///
+    /// # Errors
+    /// If the document can not be serialized to canonical JSON.
+    pub fn sign<T>(&self, document: &Document<T>) -> serde_json::Result<String>
+    where
+        T: Serialize,
+    {
+        let canonical = document.to_canonical_vec()?;
+
+        let signature = match &self.key {
+            SignerKey::HmacSha256(key) => {
+                let mut mac = hmac_sha256(key);
+                mac.update(&canonical);
+                mac.finalize().into_bytes().to_vec()
+            },
+            SignerKey::Ed25519(key) => key.sign(&canonical).to_bytes().to_vec(),
+        };
+
+        let key_id = match &self.key_id {
+            Some(key_id) => format!("keyid=\"{key_id}\", "),
+            None => String::new(),
+        };
+
+        Ok(format!(
+            "{key_id}alg=\"{}\", sig=\"{}\"",
+            self.algorithm(),
+            STANDARD.encode(signature)
+        ))
+    }
+
+    /// The name of the algorithm that this signer uses.
+    fn algorithm(&self) -> &'static str {
+        match self.key {
+            SignerKey::HmacSha256(_) => HMAC_SHA256,
+            SignerKey::Ed25519(_) => ED25519,
+        }
+    }
+}
+
+impl Debug for Signer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Signer")
+            .field("algorithm", &self.algorithm())
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The identifier given for a signing key can not be included in a `Siren-Signature` header.
+#[derive(Debug, Display)]
+#[display(fmt = "The key identifier {_0:?} can not be used in a signature")]
+pub struct InvalidKeyId(pub String);
+
+impl std::error::Error for InvalidKeyId {}
+
+/// Verifies the detached signatures of Siren documents.
+#[derive(Clone)]
+pub struct Verifier {
+    key:    VerifierKey,
+    key_id: Option<String>,
+}
+
+/// Reasons why a signature could not be verified.
+#[derive(Debug, Display)]
+pub enum VerificationError {
+    /// The signature header was not in the expected format.
+    #[display(fmt = "The signature header is malformed")]
+    MalformedSignature,
+    /// The signature was made with a different algorithm to the one the verifier uses.
+    #[display(fmt = "The signature algorithm {_0} is not supported by this verifier")]
+    UnsupportedAlgorithm(String),
+    /// The signature was made with a different key to the one the verifier uses.
+    #[display(fmt = "The signature was made with an unknown key")]
+    UnknownKey,
+    /// The signature does not match the document.
+    #[display(fmt = "The signature does not match the document")]
+    InvalidSignature,
+    /// The document could not be parsed or serialized.
+    #[display(fmt = "The document is invalid: {_0}")]
+    InvalidDocument(serde_json::Error),
+}
+
+impl std::error::Error for VerificationError {}
+
+impl Verifier {
+    /// Create a verifier for signatures that use HMAC-SHA256 with the given shared secret.
+    ///
+    /// # Parameters
+    /// - `key` - The shared secret.
+    pub fn hmac_sha256<K>(key: K) -> Self
+    where
+        K: AsRef<[u8]>,
+    {
+        Self {
+            key:    VerifierKey::HmacSha256(key.as_ref().to_vec()),
+            key_id: None,
+        }
+    }
+
+    /// Create a verifier for signatures that use Ed25519 with the given public key.
+    ///
+    /// # Parameters
+    /// - `key` - The public key.
+    #[must_use]
+    pub fn ed25519(key: VerifyingKey) -> Self {
+        Self {
+            key:    VerifierKey::Ed25519(Box::new(key)),
+            key_id: None,
+        }
+    }
+
+    /// Specify the identifier of the key. If this is specified then signatures that name a
+    /// different key, or that do not name a key at all, are rejected.
+    ///
+    /// # Parameters
+    /// - `key_id` - The identifier of the key.
+    #[must_use]
+    pub fn with_key_id<S>(mut self, key_id: S) -> Self
+    where
+        S: ToString,
+    {
+        self.key_id = Some(key_id.to_string());
+
+        self
+    }
+
+    /// Verify the signature of a response body, and deserialize the document from it if the
+    /// signature is valid.
+    ///
+    /// # Parameters
+    /// - `signature` - The value of the `Siren-Signature` header.
+    /// - `body` - The body of the response.
+    ///
+    /// # Errors
+    /// If the body is not a valid Siren document, or the signature does not match it.
+    pub fn verify<T>(&self, signature: &str, body: &[u8]) -> Result<Document<T>, VerificationError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let document: Document<Value> =
+            serde_json::from_slice(body).map_err(VerificationError::InvalidDocument)?;
+        self.verify_document(signature, &document)?;
+
+        serde_json::from_slice(body).map_err(VerificationError::InvalidDocument)
+    }
+
+    /// Verify the signature of a document.
+    ///
+    /// # Parameters
+    /// - `signature` - The value of the `Siren-Signature` header.
+    /// - `document` - The document that was signed.
+    ///
+    /// # Errors
+    /// If the signature does not match the document.
+    pub fn verify_document<T>(
+        &self,
+        signature: &str,
+        document: &Document<T>,
+    ) -> Result<(), VerificationError>
+    where
+        T: Serialize,
+    {
+        let parsed = ParsedSignature::parse(signature)?;
+
+        if let Some(expected) = &self.key_id {
+            if parsed.key_id.as_ref() != Some(expected) {
+                return Err(VerificationError::UnknownKey);
+            }
+        }
+
+        let canonical = document
+            .to_canonical_vec()
+            .map_err(VerificationError::InvalidDocument)?;
+
+        match (&self.key, parsed.algorithm.as_str()) {
+            (VerifierKey::HmacSha256(key), HMAC_SHA256) => {
+                let mut mac = hmac_sha256(key);
+                mac.update(&canonical);
+                mac.verify_slice(&parsed.signature)
+                    .map_err(|_| VerificationError::InvalidSignature)
+            },
+            (VerifierKey::Ed25519(key), ED25519) => {
+                let signature = ed25519_dalek::Signature::from_slice(&parsed.signature)
+                    .map_err(|_| VerificationError::MalformedSignature)?;
+                key.verify(&canonical, &signature)
+                    .map_err(|_| VerificationError::InvalidSignature)
+            },
+            (_, algorithm) => Err(VerificationError::UnsupportedAlgorithm(algorithm.to_owned())),
+        }
+    }
+}
+
+impl Debug for Verifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let algorithm = match self.key {
+            VerifierKey::HmacSha256(_) => HMAC_SHA256,
+            VerifierKey::Ed25519(_) => ED25519,
+        };
+
+        f.debug_struct("Verifier")
+            .field("algorithm", &algorithm)
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Response<T>
+where
+    T: Serialize,
+{
+    /// Include a `Siren-Signature` header in the response, signing the document with the given
+    /// signer.
+    ///
+    /// If the document has parts that require permissions then the header is removed when the
+    /// response is rendered by Axum, since those parts may be withheld from the caller.
+    ///
+    /// # Parameters
+    /// - `signer` - The signer to sign the document with.
+    ///
+    /// # Panics
+    /// If the document can not be serialized to canonical JSON.
+    #[must_use]
+    pub fn with_signature(mut self, signer: &Signer) -> Self {
+        let signature = signer
+            .sign(&self.document)
+            .expect("Failed to serialize document");
+
+        let value = HeaderValue::from_str(&signature)
+            .expect("Signatures only contain validated key ids and base64");
+        self.headers.insert(SIGNATURE_HEADER, value);
+
+        self
+    }
+}
+
+/// The name of the HMAC-SHA256 algorithm in signatures.
+const HMAC_SHA256: &str = "hmac-sha256";
+
+/// The name of the Ed25519 algorithm in signatures.
+const ED25519: &str = "ed25519";
+
+/// Create a new HMAC-SHA256 instance for the given key.
+fn hmac_sha256(key: &[u8]) -> Hmac<Sha256> {
+    Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length")
+}
+
+/// The parts of a `Siren-Signature` header.
+#[derive(Debug)]
+struct ParsedSignature {
+    key_id:    Option<String>,
+    algorithm: String,
+    signature: Vec<u8>,
+}
+
+impl ParsedSignature {
+    /// Parse the value of a `Siren-Signature` header.
+    fn parse(header: &str) -> Result<Self, VerificationError> {
+        let mut key_id = None;
+        let mut algorithm = None;
+        let mut signature = None;
+
+        for parameter in header.split(',') {
+            let (name, value) = parameter
+                .split_once('=')
+                .ok_or(VerificationError::MalformedSignature)?;
+            let value = value.trim().trim_matches('"').to_owned();
+
+            match name.trim() {
+                "keyid" => key_id = Some(value),
+                "alg" => algorithm = Some(value),
+                "sig" => signature = Some(value),
+                _ => {},
+            }
+        }
+
+        let (algorithm, signature) = match (algorithm, signature) {
+            (Some(algorithm), Some(signature)) => (algorithm, signature),
+            _ => return Err(VerificationError::MalformedSignature),
+        };
+        let signature = STANDARD
+            .decode(signature)
+            .map_err(|_| VerificationError::MalformedSignature)?;
+
+        Ok(Self {
+            key_id,
+            algorithm,
+            signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+    use serde_json::json;
+
+    use super::*;
+    use crate::Link;
+
+    fn document() -> Document<Value> {
+        Document::new(json!({"orderNumber": 42, "status": "pending"}))
+            .with_class("order")
+            .with_link(Link::new("/orders/42").with_rel("self"))
+    }
+
+    #[test]
+    fn hmac_round_trip() {
+        let signature = Signer::hmac_sha256("secret")
+            .with_key_id("k1")
+            .unwrap()
+            .sign(&document())
+            .unwrap();
+        check!(signature.starts_with("keyid=\"k1\", alg=\"hmac-sha256\", sig=\""));
+
+        let body = serde_json::to_vec(&document()).unwrap();
+        let verified: Document<Value> = Verifier::hmac_sha256("secret")
+            .with_key_id("k1")
+            .verify(&signature, &body)
+            .unwrap();
+        check!(verified.properties == document().properties);
+
+        let_assert!(
+            Err(VerificationError::InvalidSignature) =
+                Verifier::hmac_sha256("other").verify::<Value>(&signature, &body)
+        );
+        let_assert!(
+            Err(VerificationError::UnknownKey) = Verifier::hmac_sha256("secret")
+                .with_key_id("k2")
+                .verify::<Value>(&signature, &body)
+        );
+    }
+
+    #[test]
+    fn key_ids() {
+        let_assert!(Err(InvalidKeyId(key_id)) = Signer::hmac_sha256("secret").with_key_id("a\",b"));
+        check!(key_id == "a\",b");
+        check!(Signer::hmac_sha256("secret").with_key_id("").is_err());
+        check!(Signer::hmac_sha256("secret").with_key_id("key 1").is_err());
+
+        let signature = Signer::hmac_sha256("secret").sign(&document()).unwrap();
+        let_assert!(
+            Err(VerificationError::UnknownKey) = Verifier::hmac_sha256("secret")
+                .with_key_id("k1")
+                .verify_document(&signature, &document())
+        );
+    }
+
+    #[test]
+    fn ed25519_round_trip() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let verifier = Verifier::ed25519(key.verifying_key());
+        let signature = Signer::ed25519(key).sign(&document()).unwrap();
+
+        // Reordering the body does not change the canonical form, so the signature still holds.
+        let body = br#"{"links":[{"href":"/orders/42","rel":["self"]}],"class":["order"],
+            "properties":{"status":"pending","orderNumber":42.0}}"#;
+        check!(verifier.verify::<Value>(&signature, body).is_ok());
+
+        let tampered = br#"{"class":["order"],"properties":{"orderNumber":43,"status":"pending"},
+            "links":[{"href":"/orders/42","rel":["self"]}]}"#;
+        let_assert!(
+            Err(VerificationError::InvalidSignature) =
+                verifier.verify::<Value>(&signature, tampered)
+        );
+
+        let_assert!(
+            Err(VerificationError::UnsupportedAlgorithm(algorithm)) =
+                Verifier::hmac_sha256("secret").verify::<Value>(&signature, body)
+        );
+        check!(algorithm == "ed25519");
+    }
+
+    #[test]
+    fn response_header() {
+        let signer = Signer::hmac_sha256("secret");
+        let response = Response::new(document()).with_signature(&signer);
+
+        let header = response.headers.get(SIGNATURE_HEADER).unwrap();
+        check!(header == signer.sign(&document()).unwrap().as_str());
+        check!(Verifier::hmac_sha256("secret")
+            .verify_document(header.to_str().unwrap(), &document())
+            .is_ok());
+        let_assert!(
+            Err(VerificationError::MalformedSignature) =
+                Verifier::hmac_sha256("secret").verify_document("nonsense", &document())
+        );
+    }
+}
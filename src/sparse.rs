@@ -0,0 +1,322 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    template::parse_query, values::LinkRelation, Document, EmbeddedRepresentation, Entity, Link,
+};
+
+/// Description of how to reduce the size of a Siren document for clients that only need part of
+/// it, normally derived from the query parameters of the request:
+///
+/// - `fields` - A comma-separated list of the properties to keep. All other properties are removed.
+/// - `embed` - A comma-separated list of the embedded entities to include as full representations,
+///   matched against either the link relations or the classes of the entity. All other embedded
+///   representations are replaced with embedded links to them, and embedded links that match are
+///   replaced by the representation that they link to if a resolver is available.
+/// - `exclude` - A comma-separated list of the sections of the document to remove. These can be
+///   any of `class`, `entities`, `links`, `actions` and `title`.
+///
+/// For example, `?fields=orderNumber,status&embed=customer&exclude=actions`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseFieldset {
+    pub fields:  Option<Vec<String>>,
+    pub embed:   Option<Vec<String>>,
+    pub exclude: Vec<String>,
+}
+
+impl SparseFieldset {
+    /// Build the sparse fieldset described by the given query string.
+    ///
+    /// # Parameters
+    /// - `query` - The query string, without the leading `?`.
+    #[must_use]
+    pub fn from_query(query: &str) -> Self {
+        let mut result = Self::default();
+
+        for (name, value) in parse_query(query) {
+            let values = value
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(ToOwned::to_owned);
+
+            match name.as_str() {
+                "fields" => result.fields.get_or_insert_with(Vec::new).extend(values),
+                "embed" => result.embed.get_or_insert_with(Vec::new).extend(values),
+                "exclude" => result.exclude.extend(values),
+                _ => {},
+            }
+        }
+
+        result
+    }
+
+    /// Check if this fieldset would leave documents unchanged.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_none() && self.embed.is_none() && self.exclude.is_empty()
+    }
+
+    /// Apply this fieldset to the given document.
+    ///
+    /// Embedded links are never expanded into representations, since there is no way to resolve
+    /// them. Use [`SparseFieldset::apply_with_resolver`] for that.
+    ///
+    /// # Parameters
+    /// - `document` - The document to reduce.
+    ///
+    /// # Panics
+    /// If the properties of the document can not be serialized to JSON.
+    pub fn apply<T>(&self, document: Document<T>) -> Document<Value>
+    where
+        T: Serialize,
+    {
+        self.apply_with_resolver(document, |_| None)
+    }
+
+    /// Apply this fieldset to the given document, using the given resolver to expand embedded
+    /// links into full representations when they are requested.
+    ///
+    /// # Parameters
+    /// - `document` - The document to reduce.
+    /// - `resolver` - Callback to resolve an embedded link into the representation it links to. If
+    ///   this returns `None` then the embedded link is kept as it is.
+    ///
+    /// # Panics
+    /// If the properties of the document can not be serialized to JSON.
+    pub fn apply_with_resolver<T, F>(&self, document: Document<T>, resolver: F) -> Document<Value>
+    where
+        T: Serialize,
+        F: Fn(&Link) -> Option<EmbeddedRepresentation>,
+    {
+        let mut properties =
+            serde_json::to_value(document.properties).expect("Failed to serialize properties");
+
+        if let (Some(fields), Value::Object(members)) = (&self.fields, &mut properties) {
+            members.retain(|key, _| fields.contains(key));
+        }
+
+        let entities = match &self.embed {
+            None => document.entities,
+            Some(embed) => document
+                .entities
+                .into_iter()
+                .map(|entity| self.embed_entity(embed, entity, &resolver))
+                .collect(),
+        };
+
+        let mut result = Document {
+            class:      document.class,
+            properties,
+            entities,
+            links:      document.links,
+            actions:    document.actions,
+            title:      document.title,
+            extensions: document.extensions,
+        };
+
+        for section in &self.exclude {
+            match section.as_str() {
+                "class" => result.class.clear(),
+                "entities" => result.entities.clear(),
+                "links" => result.links.clear(),
+                "actions" => result.actions.clear(),
+                "title" => result.title = None,
+                _ => {},
+            }
+        }
+
+        result
+    }
+
+    /// Convert an embedded entity between a link and a full representation, depending on whether
+    /// it was requested to be embedded. Representations resolved from a link keep its classes and
+    /// title unless they have their own, and require its permissions as well as their own.
+    fn embed_entity<F>(&self, embed: &[String], entity: Entity, resolver: &F) -> Entity
+    where
+        F: Fn(&Link) -> Option<EmbeddedRepresentation>,
+    {
+        let matches = |rel: &[String], class: &[String]| {
+            embed.iter().any(|e| rel.contains(e) || class.contains(e))
+        };
+
+        match entity {
+            Entity::Link(link) if matches(&link.rel, &link.class) => match resolver(&link) {
+                Some(mut representation) => {
+                    representation.rel.clone_from(&link.rel);
+                    if representation.class.is_empty() {
+                        representation.class.clone_from(&link.class);
+                    }
+                    if representation.title.is_none() {
+                        representation.title.clone_from(&link.title);
+                    }
+                    for permission in &link.requires {
+                        if !representation.requires.contains(permission) {
+                            representation.requires.push(permission.clone());
+                        }
+                    }
+
+                    Entity::Representation(representation)
+                },
+                None => Entity::Link(link),
+            },
+            Entity::Representation(representation)
+                if !matches(&representation.rel, &representation.class) =>
+            {
+                match representation_link(&representation) {
+                    Some(link) => Entity::Link(link),
+                    None => Entity::Representation(representation),
+                }
+            },
+            entity => entity,
+        }
+    }
+}
+
+/// Build an embedded link to the given representation, using its self link. Returns `None` if
+/// the representation does not have a self link.
+fn representation_link(representation: &EmbeddedRepresentation) -> Option<Link> {
+    let rel = LinkRelation::SelfLink.to_string();
+    let self_link = representation.links.iter().find(|l| l.rel.contains(&rel))?;
+
+    Some(Link {
+        rel:        representation.rel.clone(),
+        class:      representation.class.clone(),
+        href:       self_link.href.clone(),
+        title:      representation.title.clone(),
+        media_type: self_link.media_type.clone(),
+        requires:   representation.requires.clone(),
+        extensions: representation.extensions.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::json;
+
+    use super::*;
+    use crate::Action;
+
+    fn example() -> Document<Value> {
+        Document::new(json!({
+            "orderNumber": 42,
+            "itemCount": 3,
+            "status": "pending"
+        }))
+        .with_class("order")
+        .with_embedded_link(
+            Link::new("http://api.x.io/orders/42/items")
+                .with_class("items")
+                .with_rel("http://x.io/rels/order-items"),
+        )
+        .with_embedded_representation(
+            EmbeddedRepresentation::new(json!({"customerId": "pj123"}))
+                .with_class("customer")
+                .with_rel("http://x.io/rels/customer")
+                .with_link(
+                    Link::new("http://api.x.io/customers/pj123").with_rel(LinkRelation::SelfLink),
+                ),
+        )
+        .with_action(Action::new("add-item", "http://api.x.io/orders/42/items"))
+        .with_link(Link::new("http://api.x.io/orders/42").with_rel(LinkRelation::SelfLink))
+        .with_title("Order 42")
+    }
+
+    #[test]
+    fn parse_query() {
+        let fieldset =
+            SparseFieldset::from_query("fields=orderNumber,status&embed=customer&exclude=actions");
+
+        check!(
+            fieldset
+                == SparseFieldset {
+                    fields:  Some(vec!["orderNumber".to_owned(), "status".to_owned()]),
+                    embed:   Some(vec!["customer".to_owned()]),
+                    exclude: vec!["actions".to_owned()],
+                }
+        );
+        check!(SparseFieldset::from_query("page=2").is_empty());
+    }
+
+    #[test]
+    fn empty_fieldset() {
+        let document = SparseFieldset::default().apply(example());
+
+        check!(
+            serde_json::to_value(&document).unwrap() == serde_json::to_value(example()).unwrap()
+        );
+    }
+
+    #[test]
+    fn prune_fields_and_sections() {
+        let fieldset =
+            SparseFieldset::from_query("fields=orderNumber,status&exclude=actions,title");
+        let document = fieldset.apply(example());
+
+        check!(document.properties == json!({"orderNumber": 42, "status": "pending"}));
+        check!(document.actions.is_empty());
+        check!(document.title == None);
+        check!(document.links.len() == 1);
+        check!(document.entities.len() == 2);
+    }
+
+    #[test]
+    fn collapse_representations() {
+        let fieldset = SparseFieldset::from_query("embed=");
+        let document = fieldset.apply(example());
+
+        let serialized = serde_json::to_value(&document).unwrap();
+        check!(
+            serialized["entities"][1]
+                == json!({
+                    "rel": ["http://x.io/rels/customer"],
+                    "class": ["customer"],
+                    "href": "http://api.x.io/customers/pj123"
+                })
+        );
+    }
+
+    #[test]
+    fn expand_links() {
+        let fieldset = SparseFieldset::from_query("embed=customer,items");
+        let document = fieldset.apply_with_resolver(example(), |link| {
+            Some(EmbeddedRepresentation::new(json!({ "href": link.href })))
+        });
+
+        let serialized = serde_json::to_value(&document).unwrap();
+        check!(
+            serialized["entities"][0]
+                == json!({
+                    "rel": ["http://x.io/rels/order-items"],
+                    "class": ["items"],
+                    "properties": { "href": "http://api.x.io/orders/42/items" }
+                })
+        );
+        check!(serialized["entities"][1]["properties"] == json!({"customerId": "pj123"}));
+    }
+
+    #[test]
+    fn expand_restricted_links() {
+        let document = Document::new(json!({})).with_embedded_link(
+            Link::new("http://api.x.io/orders/42/invoice")
+                .with_rel("invoice")
+                .with_class("invoice")
+                .requires("invoices:read"),
+        );
+
+        let fieldset = SparseFieldset::from_query("embed=invoice");
+        let document = fieldset.apply_with_resolver(document, |link| {
+            Some(EmbeddedRepresentation::new(json!({ "href": link.href })))
+        });
+
+        match &document.entities[0] {
+            Entity::Representation(invoice) => {
+                check!(invoice.class == vec!["invoice"]);
+                check!(invoice.requires == vec!["invoices:read"]);
+            },
+            Entity::Link(_) => panic!("The invoice was not embedded"),
+        }
+        check!(document.authorize(&|_: &str| false).entities.is_empty());
+    }
+}
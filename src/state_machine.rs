@@ -0,0 +1,236 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use std::fmt::Display;
+
+use http::StatusCode;
+use serde::Serialize;
+
+use crate::{Action, Document, ErrorDocument};
+
+/// Declarative description of the lifecycle of a resource, listing the actions that are valid in
+/// each state and the state that each of them moves the resource into.
+///
+/// ```rust
+/// # use http_siren::{Action, Document, StateMachine};
+/// # use serde_json::json;
+/// let machine = StateMachine::new()
+///     .with_transition("pending", Action::new("pay", "/orders/42/payment"), "paid")
+///     .with_transition("pending", Action::new("cancel", "/orders/42/cancel"), "cancelled")
+///     .with_action("pending", Action::new("add-item", "/orders/42/items"))
+///     .with_transition("paid", Action::new("ship", "/orders/42/shipment"), "shipped");
+///
+/// let document = machine.apply(Document::new(json!({"orderNumber": 42})), &"pending");
+/// assert_eq!(document.actions.len(), 3);
+///
+/// assert_eq!(machine.validate(&"pending", "pay").unwrap(), &"paid");
+/// assert!(machine.validate(&"shipped", "pay").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StateMachine<S> {
+    actions: Vec<StateAction<S>>,
+}
+
+/// An action that is valid in a single state.
+#[derive(Debug, Clone)]
+struct StateAction<S> {
+    state:  S,
+    action: Action,
+    target: Option<S>,
+}
+
+impl<S> Default for StateMachine<S> {
+    fn default() -> Self {
+        Self { actions: vec![] }
+    }
+}
+
+impl<S> StateMachine<S>
+where
+    S: PartialEq + Display,
+{
+    /// Create a new state machine, with no states.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify an action that moves the resource from one state to another.
+    ///
+    /// # Parameters
+    /// - `from` - The state that the action is valid in.
+    /// - `action` - The action.
+    /// - `to` - The state that the action moves the resource into.
+    #[must_use]
+    pub fn with_transition(mut self, from: S, action: Action, to: S) -> Self {
+        self.actions.push(StateAction {
+            state: from,
+            action,
+            target: Some(to),
+        });
+
+        self
+    }
+
+    /// Specify an action that is valid in a state, but doesn't change the state of the resource.
+    ///
+    /// # Parameters
+    /// - `state` - The state that the action is valid in.
+    /// - `action` - The action.
+    #[must_use]
+    pub fn with_action(mut self, state: S, action: Action) -> Self {
+        self.actions.push(StateAction {
+            state,
+            action,
+            target: None,
+        });
+
+        self
+    }
+
+    /// Get the actions that are valid in the given state, in the order they were specified.
+    ///
+    /// # Parameters
+    /// - `state` - The current state of the resource.
+    #[must_use]
+    pub fn actions(&self, state: &S) -> Vec<&Action> {
+        self.actions
+            .iter()
+            .filter(|a| &a.state == state)
+            .map(|a| &a.action)
+            .collect()
+    }
+
+    /// Add the actions that are valid in the given state to a document.
+    ///
+    /// # Parameters
+    /// - `document` - The document to add the actions to.
+    /// - `state` - The current state of the resource.
+    pub fn apply<T>(&self, mut document: Document<T>, state: &S) -> Document<T>
+    where
+        T: Serialize,
+    {
+        document.actions.extend(self.actions(state).into_iter().cloned());
+
+        document
+    }
+
+    /// Check that the named action may be submitted while the resource is in the given state,
+    /// and determine the state that the resource moves into.
+    ///
+    /// # Parameters
+    /// - `state` - The current state of the resource.
+    /// - `action` - The name of the action being submitted.
+    ///
+    /// # Errors
+    /// If the action is not valid in the current state, a `409 Conflict` error document that
+    /// lists the actions that are valid instead.
+    #[allow(clippy::result_large_err)] // The error is normally returned straight from a handler
+    pub fn validate<'a>(&'a self, state: &'a S, action: &str) -> Result<&'a S, ErrorDocument> {
+        let found = self
+            .actions
+            .iter()
+            .find(|a| &a.state == state && a.action.name == action);
+
+        let found = if let Some(found) = found {
+            found
+        } else {
+            let mut error = ErrorDocument::new(StatusCode::CONFLICT).with_detail(format!(
+                "The action {action} is not allowed while the resource is {state}"
+            ));
+            error
+                .document
+                .actions
+                .extend(self.actions(state).into_iter().cloned());
+
+            return Err(error);
+        };
+
+        Ok(found.target.as_ref().unwrap_or(state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, derive_more::Display)]
+    enum OrderState {
+        #[display(fmt = "pending")]
+        Pending,
+        #[display(fmt = "paid")]
+        Paid,
+        #[display(fmt = "shipped")]
+        Shipped,
+    }
+
+    fn machine() -> StateMachine<OrderState> {
+        StateMachine::new()
+            .with_transition(
+                OrderState::Pending,
+                Action::new("pay", "/orders/42/payment"),
+                OrderState::Paid,
+            )
+            .with_action(
+                OrderState::Pending,
+                Action::new("add-item", "/orders/42/items"),
+            )
+            .with_transition(
+                OrderState::Paid,
+                Action::new("ship", "/orders/42/shipment"),
+                OrderState::Shipped,
+            )
+    }
+
+    #[test]
+    fn actions_for_state() {
+        let names = |state| {
+            machine()
+                .apply(Document::new(json!({})), &state)
+                .actions
+                .into_iter()
+                .map(|a| a.name)
+                .collect::<Vec<_>>()
+        };
+
+        check!(names(OrderState::Pending) == vec!["pay", "add-item"]);
+        check!(names(OrderState::Paid) == vec!["ship"]);
+        check!(names(OrderState::Shipped).is_empty());
+    }
+
+    #[test]
+    fn valid_submissions() {
+        let machine = machine();
+
+        check!(machine.validate(&OrderState::Pending, "pay").unwrap() == &OrderState::Paid);
+        check!(
+            machine
+                .validate(&OrderState::Pending, "add-item")
+                .unwrap()
+                == &OrderState::Pending
+        );
+    }
+
+    #[test]
+    fn invalid_submission() {
+        let_assert!(Err(error) = machine().validate(&OrderState::Paid, "pay"));
+
+        check!(error.status_code() == StatusCode::CONFLICT);
+        check!(
+            serde_json::to_value(&error.document).unwrap()
+                == json!({
+                    "class": ["error"],
+                    "properties": {
+                        "type": "about:blank",
+                        "title": "Conflict",
+                        "status": 409,
+                        "detail": "The action pay is not allowed while the resource is paid"
+                    },
+                    "actions": [{"name": "ship", "href": "/orders/42/shipment"}],
+                    "title": "Conflict"
+                })
+        );
+    }
+}
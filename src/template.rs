@@ -0,0 +1,181 @@
+//! Minimal support for expanding URI Templates, as described in
+//! [RFC 6570](https://www.rfc-editor.org/rfc/rfc6570).
+//!
+//! Only simple string expansion (`{var}`), form-style query expansion (`{?var}`) and form-style
+//! query continuation (`{&var}`) are supported, which is enough for building links to other pages
+//! of a collection. Variables without a value are omitted from query expansions, and expand to an
+//! empty string otherwise.
+
+/// Expand the given URI Template using the given variables.
+///
+/// # Parameters
+/// - `template` - The URI Template to expand.
+/// - `variables` - The variables to expand the template with.
+pub(crate) fn expand(template: &str, variables: &[(&str, Option<String>)]) -> String {
+    let lookup = |name: &str| {
+        variables
+            .iter()
+            .find(|(n, _)| *n == name)
+            .and_then(|(_, v)| v.as_deref())
+    };
+
+    let mut result = String::with_capacity(template.len());
+    let mut remaining = template;
+
+    while let Some(start) = remaining.find('{') {
+        result.push_str(&remaining[..start]);
+
+        let end = if let Some(end) = remaining[start..].find('}') {
+            end
+        } else {
+            remaining = &remaining[start..];
+            break;
+        };
+        let expression = &remaining[start + 1..start + end];
+        remaining = &remaining[start + end + 1..];
+
+        match expression.chars().next() {
+            Some(operator @ ('?' | '&')) => {
+                let mut first = operator == '?';
+                for name in expression[1..].split(',') {
+                    if let Some(value) = lookup(name) {
+                        result.push(if first { '?' } else { '&' });
+                        result.push_str(name);
+                        result.push('=');
+                        result.push_str(&encode(value));
+                        first = false;
+                    }
+                }
+            },
+            _ => {
+                if let Some(value) = lookup(expression) {
+                    result.push_str(&encode(value));
+                }
+            },
+        }
+    }
+
+    result.push_str(remaining);
+
+    result
+}
+
+/// Percent-encode every character of the value apart from the unreserved characters.
+fn encode(value: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let mut result = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            result.push(char::from(byte));
+        } else {
+            result.push('%');
+            result.push(char::from(HEX[usize::from(byte >> 4)]));
+            result.push(char::from(HEX[usize::from(byte & 0x0F)]));
+        }
+    }
+
+    result
+}
+
+/// Decode a single `application/x-www-form-urlencoded` value, converting `+` to a space and
+/// decoding any percent-encoded bytes. Invalid escapes are kept as they are.
+pub(crate) fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => result.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let high = char::from(bytes[i + 1]).to_digit(16);
+                let low = char::from(bytes[i + 2]).to_digit(16);
+
+                match (high, low) {
+                    (Some(high), Some(low)) => {
+                        result.extend(u8::try_from(high * 16 + low));
+                        i += 2;
+                    },
+                    _ => result.push(b'%'),
+                }
+            },
+            byte => result.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+/// Parse an `application/x-www-form-urlencoded` query string into pairs of names and values.
+pub(crate) fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((name, value)) => (decode(name), decode(value)),
+            None => (decode(pair), String::new()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+
+    #[test]
+    fn simple() {
+        let result = expand("/orders/{id}/items", &[("id", Some("a b/c".to_owned()))]);
+
+        check!(result == "/orders/a%20b%2Fc/items");
+    }
+
+    #[test]
+    fn query() {
+        let result = expand(
+            "/orders{?offset,limit,sort}",
+            &[
+                ("offset", Some("20".to_owned())),
+                ("limit", Some("10".to_owned())),
+                ("sort", None),
+            ],
+        );
+
+        check!(result == "/orders?offset=20&limit=10");
+    }
+
+    #[test]
+    fn query_continuation() {
+        let result = expand(
+            "/orders?sort=name{&cursor,limit}",
+            &[("cursor", None), ("limit", Some("10".to_owned()))],
+        );
+
+        check!(result == "/orders?sort=name&limit=10");
+    }
+
+    #[test]
+    fn decoding() {
+        check!(decode("a+b%2Cc") == "a b,c");
+        check!(decode("100%") == "100%");
+        check!(decode("%zz") == "%zz");
+        check!(
+            parse_query("fields=a%2Cb&embed=&flag")
+                == vec![
+                    ("fields".to_owned(), "a,b".to_owned()),
+                    ("embed".to_owned(), String::new()),
+                    ("flag".to_owned(), String::new()),
+                ]
+        );
+    }
+
+    #[test]
+    fn no_expressions() {
+        check!(expand("/orders", &[]) == "/orders");
+        check!(expand("/orders/{id", &[]) == "/orders/{id");
+    }
+}
@@ -0,0 +1,764 @@
+//! Helpers for asserting on Siren documents and responses in tests.
+//!
+//! The entry point is the [`assert_siren!`](crate::assert_siren) macro, which accepts anything that
+//! can be rendered as a Siren document and returns a [`SirenAssertion`] to chain checks on:
+//!
+//! ```rust
+//! # use serde_json::json;
+//! # use http_siren::{assert_siren, Action, Document, Link};
+//! let document = Document::new(json!({"orderNumber": 42}))
+//!     .with_class("order")
+//!     .with_link(Link::new("/orders/42").with_rel("self"))
+//!     .with_action(Action::new("add-item", "/orders/42/items").with_method(http::Method::POST));
+//!
+//! assert_siren!(document)
+//!     .has_class("order")
+//!     .has_property("orderNumber", json!(42))
+//!     .has_link("self", "/orders/42")
+//!     .has_action("add-item", |a| a.method("POST"));
+//! ```
+//!
+//! Every failed check panics with a message describing what was expected, what was actually
+//! present, and the pretty-printed JSON of the part of the document being checked.
+
+// Every assertion panics on failure by design, and the end of an assertion chain is expected to be
+// discarded, so neither panic docs nor `#[must_use]` make sense here.
+#![allow(
+    clippy::missing_panics_doc,
+    clippy::must_use_candidate,
+    clippy::needless_pass_by_value,
+    clippy::return_self_not_must_use
+)]
+
+use http::{HeaderMap, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Document, Response};
+
+/// Build a [`SirenAssertion`] for the given value.
+///
+/// The value can be anything implementing [`ToSirenValue`], which includes [`Document`],
+/// [`Response`] and raw [`serde_json::Value`]s.
+#[macro_export]
+macro_rules! assert_siren {
+    ($value:expr) => {
+        $crate::testing::SirenAssertion::new(&$value)
+    };
+}
+
+/// Trait for anything that can be rendered into the JSON of a Siren document for assertions.
+pub trait ToSirenValue {
+    /// Render this value as JSON.
+    fn to_siren_value(&self) -> Value;
+
+    /// The HTTP status code that this value represents, if any.
+    fn status_code(&self) -> Option<StatusCode> {
+        None
+    }
+
+    /// The HTTP headers that this value represents, if any.
+    fn headers(&self) -> Option<HeaderMap> {
+        None
+    }
+}
+
+impl ToSirenValue for Value {
+    fn to_siren_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl<T> ToSirenValue for Document<T>
+where
+    T: Serialize,
+{
+    fn to_siren_value(&self) -> Value {
+        serde_json::to_value(self).expect("Failed to serialize document")
+    }
+}
+
+impl<T> ToSirenValue for Response<T>
+where
+    T: Serialize,
+{
+    fn to_siren_value(&self) -> Value {
+        self.document.to_siren_value()
+    }
+
+    fn status_code(&self) -> Option<StatusCode> {
+        Some(self.status_code)
+    }
+
+    fn headers(&self) -> Option<HeaderMap> {
+        Some(self.headers.clone())
+    }
+}
+
+/// Assertions that can be made about a Siren document or embedded representation.
+pub struct SirenAssertion {
+    value:       Value,
+    status_code: Option<StatusCode>,
+    headers:     Option<HeaderMap>,
+}
+
+/// Assertions that can be made about a single action.
+pub struct ActionAssertion<'a> {
+    value: &'a Value,
+}
+
+/// Assertions that can be made about a single field within an action.
+pub struct FieldAssertion<'a> {
+    value: &'a Value,
+}
+
+impl SirenAssertion {
+    /// Create a new set of assertions for the given value.
+    ///
+    /// # Parameters
+    /// - `value` - The value to make assertions about.
+    pub fn new<V>(value: &V) -> Self
+    where
+        V: ToSirenValue + ?Sized,
+    {
+        Self {
+            value:       value.to_siren_value(),
+            status_code: value.status_code(),
+            headers:     value.headers(),
+        }
+    }
+
+    /// Create a new set of assertions from a rendered Axum response.
+    ///
+    /// # Parameters
+    /// - `response` - The response to make assertions about.
+    ///
+    /// # Panics
+    /// If the response body can not be read or is not valid JSON.
+    #[cfg(feature = "axum")]
+    pub async fn from_axum_response(response: axum::response::Response) -> Self {
+        let status_code = response.status();
+        let headers = response.headers().clone();
+
+        let bytes = crate::axum::read_body(response.into_body())
+            .await
+            .expect("Failed to read response body");
+
+        let value = serde_json::from_slice(&bytes).expect("Response body was not valid JSON");
+
+        Self {
+            value,
+            status_code: Some(status_code),
+            headers:     Some(headers),
+        }
+    }
+
+    /// The raw JSON of the document being asserted on.
+    #[must_use]
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Assert that the response has the given status code.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected status code.
+    #[track_caller]
+    pub fn has_status_code(self, expected: StatusCode) -> Self {
+        match self.status_code {
+            Some(actual) if actual == expected => {},
+            Some(actual) => panic!("Expected status code {expected} but was {actual}"),
+            None => panic!("Expected status code {expected} but the value was not a response"),
+        }
+
+        self
+    }
+
+    /// Assert that the response has a header with the given value.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the header.
+    /// - `expected` - The expected value of the header.
+    #[track_caller]
+    pub fn has_header(self, name: &str, expected: &str) -> Self {
+        let headers = self
+            .headers
+            .as_ref()
+            .unwrap_or_else(|| panic!("Expected header {name} but the value was not a response"));
+        let actual: Vec<_> = headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect();
+
+        assert!(
+            actual.contains(&expected),
+            "Expected header {name} to be `{expected}` but was {actual:?}"
+        );
+
+        self
+    }
+
+    /// Assert that the document has the given class.
+    ///
+    /// # Parameters
+    /// - `class` - The class that is expected.
+    #[track_caller]
+    pub fn has_class(self, class: &str) -> Self {
+        let classes = strings(&self.value, "class");
+        if !classes.contains(&class) {
+            fail(
+                &format!("Expected class `{class}` but classes were {classes:?}"),
+                &self.value,
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the document has the given title.
+    ///
+    /// # Parameters
+    /// - `title` - The title that is expected.
+    #[track_caller]
+    pub fn has_title(self, title: &str) -> Self {
+        let actual = self.value.get("title").and_then(Value::as_str);
+        if actual != Some(title) {
+            fail(
+                &format!("Expected title `{title}` but was {actual:?}"),
+                &self.value,
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the properties of the document have a given value at the given key.
+    ///
+    /// # Parameters
+    /// - `key` - The key of the property.
+    /// - `expected` - The expected value of the property.
+    #[track_caller]
+    pub fn has_property<V>(self, key: &str, expected: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        let expected = expected.into();
+        let actual = self.value.get("properties").and_then(|p| p.get(key));
+        if actual != Some(&expected) {
+            fail(
+                &format!("Expected property `{key}` to be {expected} but was {actual:?}"),
+                self.value.get("properties").unwrap_or(&Value::Null),
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the properties of the document are exactly the given value.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected properties.
+    #[track_caller]
+    pub fn has_properties<V>(self, expected: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        let expected = expected.into();
+        let actual = self.value.get("properties").unwrap_or(&Value::Null);
+        if actual != &expected {
+            let mut changes = vec![];
+            diff(&expected, actual, "$", &mut changes);
+
+            fail(
+                &format!(
+                    "Expected properties to match but they differed (- missing, + unexpected, \
+                     ~ changed):\n{}",
+                    changes.join("\n")
+                ),
+                actual,
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the document has a link with the given relation and href.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation of the link.
+    /// - `href` - The href of the link.
+    #[track_caller]
+    pub fn has_link<R>(self, rel: R, href: &str) -> Self
+    where
+        R: ToString,
+    {
+        let rel = rel.to_string();
+        let links = array(&self.value, "links");
+        if !links.iter().any(|l| link_matches(l, &rel, href)) {
+            fail(
+                &format!("Expected link with rel `{rel}` and href `{href}`"),
+                self.value.get("links").unwrap_or(&Value::Null),
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the document has no link with the given relation.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation of the link.
+    #[track_caller]
+    pub fn has_no_link<R>(self, rel: R) -> Self
+    where
+        R: ToString,
+    {
+        let rel = rel.to_string();
+        let links = array(&self.value, "links");
+        if links.iter().any(|l| strings(l, "rel").contains(&rel.as_str())) {
+            fail(
+                &format!("Expected no link with rel `{rel}`"),
+                self.value.get("links").unwrap_or(&Value::Null),
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the document has an embedded link with the given relation and href.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation of the embedded link.
+    /// - `href` - The href of the embedded link.
+    #[track_caller]
+    pub fn has_embedded_link<R>(self, rel: R, href: &str) -> Self
+    where
+        R: ToString,
+    {
+        let rel = rel.to_string();
+        let entities = array(&self.value, "entities");
+        if !entities.iter().any(|e| link_matches(e, &rel, href)) {
+            fail(
+                &format!("Expected embedded link with rel `{rel}` and href `{href}`"),
+                self.value.get("entities").unwrap_or(&Value::Null),
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the document has an embedded representation with the given relation, and then
+    /// make further assertions about it.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation of the embedded representation.
+    /// - `f` - Lambda to make assertions about the embedded representation.
+    #[track_caller]
+    pub fn has_embedded_representation<R, F>(self, rel: R, f: F) -> Self
+    where
+        R: ToString,
+        F: FnOnce(SirenAssertion) -> SirenAssertion,
+    {
+        let rel = rel.to_string();
+        let entities = array(&self.value, "entities");
+        let entity = entities.iter().find(|e| {
+            e.get("href").is_none() && strings(e, "rel").contains(&rel.as_str())
+        });
+
+        match entity {
+            Some(entity) => {
+                f(SirenAssertion::new(entity));
+            },
+            None => fail(
+                &format!("Expected embedded representation with rel `{rel}`"),
+                self.value.get("entities").unwrap_or(&Value::Null),
+            ),
+        }
+
+        self
+    }
+
+    /// Assert that the document has an action with the given name, and then make further
+    /// assertions about it.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the action.
+    /// - `f` - Lambda to make assertions about the action.
+    #[track_caller]
+    pub fn has_action<F>(self, name: &str, f: F) -> Self
+    where
+        F: FnOnce(ActionAssertion<'_>) -> ActionAssertion<'_>,
+    {
+        let actions = array(&self.value, "actions");
+        let action = actions
+            .iter()
+            .find(|a| a.get("name").and_then(Value::as_str) == Some(name));
+
+        match action {
+            Some(action) => {
+                f(ActionAssertion { value: action });
+            },
+            None => fail(
+                &format!("Expected action named `{name}`"),
+                self.value.get("actions").unwrap_or(&Value::Null),
+            ),
+        }
+
+        self
+    }
+
+    /// Assert that the document has no action with the given name.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the action.
+    #[track_caller]
+    pub fn has_no_action(self, name: &str) -> Self {
+        let actions = array(&self.value, "actions");
+        if actions
+            .iter()
+            .any(|a| a.get("name").and_then(Value::as_str) == Some(name))
+        {
+            fail(
+                &format!("Expected no action named `{name}`"),
+                self.value.get("actions").unwrap_or(&Value::Null),
+            );
+        }
+
+        self
+    }
+}
+
+impl<'a> ActionAssertion<'a> {
+    /// Assert that the action has the given HTTP method.
+    ///
+    /// Actions without an explicit method are treated as having a method of `GET`, as per the
+    /// Siren specification.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected HTTP method.
+    #[track_caller]
+    pub fn method<M>(self, expected: M) -> Self
+    where
+        M: ToString,
+    {
+        let expected = expected.to_string();
+        let actual = self
+            .value
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or("GET");
+        if actual != expected {
+            fail(
+                &format!("Expected action method `{expected}` but was `{actual}`"),
+                self.value,
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the action has the given href.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected href.
+    #[track_caller]
+    pub fn href(self, expected: &str) -> Self {
+        self.string_member("href", expected)
+    }
+
+    /// Assert that the action has the given title.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected title.
+    #[track_caller]
+    pub fn title(self, expected: &str) -> Self {
+        self.string_member("title", expected)
+    }
+
+    /// Assert that the action has the given media type.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected media type.
+    #[track_caller]
+    pub fn media_type(self, expected: &str) -> Self {
+        self.string_member("type", expected)
+    }
+
+    /// Assert that the action has the given class.
+    ///
+    /// # Parameters
+    /// - `class` - The class that is expected.
+    #[track_caller]
+    pub fn has_class(self, class: &str) -> Self {
+        let classes = strings(self.value, "class");
+        if !classes.contains(&class) {
+            fail(
+                &format!("Expected action class `{class}` but classes were {classes:?}"),
+                self.value,
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the action has a field with the given name, and then make further assertions
+    /// about it.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the field.
+    /// - `f` - Lambda to make assertions about the field.
+    #[track_caller]
+    pub fn has_field<F>(self, name: &str, f: F) -> Self
+    where
+        F: FnOnce(FieldAssertion<'a>) -> FieldAssertion<'a>,
+    {
+        let fields = array(self.value, "fields");
+        let field = fields
+            .iter()
+            .find(|a| a.get("name").and_then(Value::as_str) == Some(name));
+
+        match field {
+            Some(field) => {
+                f(FieldAssertion { value: field });
+            },
+            None => fail(&format!("Expected field named `{name}`"), self.value),
+        }
+
+        self
+    }
+
+    #[track_caller]
+    fn string_member(self, member: &str, expected: &str) -> Self {
+        let actual = self.value.get(member).and_then(Value::as_str);
+        if actual != Some(expected) {
+            fail(
+                &format!("Expected action {member} `{expected}` but was {actual:?}"),
+                self.value,
+            );
+        }
+
+        self
+    }
+}
+
+impl FieldAssertion<'_> {
+    /// Assert that the field has the given input type.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected input type.
+    #[track_caller]
+    pub fn input_type<S>(self, expected: S) -> Self
+    where
+        S: ToString,
+    {
+        let expected = expected.to_string();
+        let actual = self.value.get("type").and_then(Value::as_str);
+        if actual != Some(expected.as_str()) {
+            fail(
+                &format!("Expected field type `{expected}` but was {actual:?}"),
+                self.value,
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the field has the given value.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected value.
+    #[track_caller]
+    pub fn value<V>(self, expected: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        let expected = expected.into();
+        let actual = self.value.get("value");
+        if actual != Some(&expected) {
+            fail(
+                &format!("Expected field value {expected} but was {actual:?}"),
+                self.value,
+            );
+        }
+
+        self
+    }
+
+    /// Assert that the field has the given title.
+    ///
+    /// # Parameters
+    /// - `expected` - The expected title.
+    #[track_caller]
+    pub fn title(self, expected: &str) -> Self {
+        let actual = self.value.get("title").and_then(Value::as_str);
+        if actual != Some(expected) {
+            fail(
+                &format!("Expected field title `{expected}` but was {actual:?}"),
+                self.value,
+            );
+        }
+
+        self
+    }
+}
+
+/// Get the array stored at the given key, or an empty slice if there is none.
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Get the strings stored in the array at the given key.
+fn strings<'a>(value: &'a Value, key: &str) -> Vec<&'a str> {
+    array(value, key).iter().filter_map(Value::as_str).collect()
+}
+
+/// Check if the given value is a link with the given relation and href.
+fn link_matches(value: &Value, rel: &str, href: &str) -> bool {
+    value.get("href").and_then(Value::as_str) == Some(href) && strings(value, "rel").contains(&rel)
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// Compare the expected value with the actual one, recording a line for every path that is
+/// missing from the actual value, present only in the actual value, or different between the two.
+fn diff(expected: &Value, actual: &Value, path: &str, changes: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected) in expected {
+                let path = format!("{path}.{key}");
+                match actual.get(key) {
+                    Some(actual) => diff(expected, actual, &path, changes),
+                    None => changes.push(format!("- {path}: {expected}")),
+                }
+            }
+            for (key, actual) in actual {
+                if !expected.contains_key(key) {
+                    changes.push(format!("+ {path}.{key}: {actual}"));
+                }
+            }
+        },
+        (Value::Array(expected), Value::Array(actual)) => {
+            for (index, expected) in expected.iter().enumerate() {
+                let path = format!("{path}[{index}]");
+                match actual.get(index) {
+                    Some(actual) => diff(expected, actual, &path, changes),
+                    None => changes.push(format!("- {path}: {expected}")),
+                }
+            }
+            for (index, actual) in actual.iter().enumerate().skip(expected.len()) {
+                changes.push(format!("+ {path}[{index}]: {actual}"));
+            }
+        },
+        (expected, actual) if expected != actual => {
+            changes.push(format!("~ {path}: {expected} -> {actual}"));
+        },
+        _ => {},
+    }
+}
+
+#[track_caller]
+fn fail(message: &str, context: &Value) -> ! {
+    panic!("{message}\n\nActual:\n{}", pretty(context));
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        values::{FieldTypes, HttpMethods, LinkRelation},
+        Action, Document, EmbeddedRepresentation, Field, Link, Response,
+    };
+
+    fn example() -> Document<serde_json::Value> {
+        Document::new(json!({
+            "orderNumber": 42,
+            "status": "pending"
+        }))
+        .with_class("order")
+        .with_embedded_link(
+            Link::new("http://api.x.io/orders/42/items").with_rel("http://x.io/rels/order-items"),
+        )
+        .with_embedded_representation(
+            EmbeddedRepresentation::new(json!({"customerId": "pj123"}))
+                .with_class("customer")
+                .with_rel("http://x.io/rels/customer"),
+        )
+        .with_action(
+            Action::new("add-item", "http://api.x.io/orders/42/items")
+                .with_method(HttpMethods::POST)
+                .with_field(
+                    Field::new("orderNumber")
+                        .with_type(FieldTypes::Hidden)
+                        .with_value("42"),
+                ),
+        )
+        .with_link(Link::new("http://api.x.io/orders/42").with_rel(LinkRelation::SelfLink))
+    }
+
+    #[test]
+    fn passing_assertions() {
+        assert_siren!(example())
+            .has_class("order")
+            .has_property("orderNumber", 42)
+            .has_link(LinkRelation::SelfLink, "http://api.x.io/orders/42")
+            .has_no_link(LinkRelation::Next)
+            .has_embedded_link("http://x.io/rels/order-items", "http://api.x.io/orders/42/items")
+            .has_embedded_representation("http://x.io/rels/customer", |e| {
+                e.has_class("customer").has_property("customerId", "pj123")
+            })
+            .has_action("add-item", |a| {
+                a.method("POST")
+                    .href("http://api.x.io/orders/42/items")
+                    .has_field("orderNumber", |f| f.input_type(FieldTypes::Hidden).value("42"))
+            })
+            .has_no_action("remove-item");
+    }
+
+    #[test]
+    fn response_assertions() {
+        let response = Response::new(example()).with_status_code(http::StatusCode::CREATED);
+
+        assert_siren!(response)
+            .has_status_code(http::StatusCode::CREATED)
+            .has_class("order");
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected class `customer` but classes were [\"order\"]")]
+    fn missing_class() {
+        assert_siren!(example()).has_class("customer");
+    }
+
+    #[test]
+    #[should_panic(expected = "~ $.orderNumber: 43 -> 42\n- $.total: 10\n+ $.status: \"pending\"")]
+    fn different_properties() {
+        assert_siren!(example()).has_properties(json!({"orderNumber": 43, "total": 10}));
+    }
+
+    #[test]
+    fn diff_arrays() {
+        let mut changes = vec![];
+        super::diff(&json!({"a": [1, 2]}), &json!({"a": [1, 3, 4]}), "$", &mut changes);
+
+        assert_eq!(changes, vec!["~ $.a[1]: 2 -> 3", "+ $.a[2]: 4"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected link with rel `next`")]
+    fn missing_link() {
+        assert_siren!(example()).has_link(LinkRelation::Next, "http://api.x.io/orders/43");
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected action method `PUT` but was `POST`")]
+    fn wrong_action_method() {
+        assert_siren!(example()).has_action("add-item", |a| a.method("PUT"));
+    }
+}
@@ -0,0 +1,236 @@
+/// The components of a URI reference, as described by
+/// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-3).
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Reference<'a> {
+    pub scheme:    Option<&'a str>,
+    pub authority: Option<&'a str>,
+    pub path:      &'a str,
+    pub query:     Option<&'a str>,
+    pub fragment:  Option<&'a str>,
+}
+
+impl<'a> Reference<'a> {
+    /// Split a URI reference into its components, in the same way as the regular expression from
+    /// [appendix B of RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#appendix-B).
+    pub fn parse(value: &'a str) -> Self {
+        let (rest, fragment) = match value.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (value, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (scheme, rest) = match rest.split_once(':') {
+            Some((scheme, rest)) if !scheme.is_empty() && !scheme.contains('/') => {
+                (Some(scheme), rest)
+            },
+            _ => (None, rest),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            },
+            None => (None, rest),
+        };
+
+        Self {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Resolve a URI reference against the URI of the resource that it appears in, as described by
+/// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-5.2). The base may itself be a
+/// relative reference, such as an absolute path, in which case the result is relative as well.
+///
+/// # Parameters
+/// - `base` - The URI that the reference is relative to.
+/// - `reference` - The URI reference to resolve.
+pub(crate) fn resolve(base: &str, reference: &str) -> String {
+    let base = Reference::parse(base);
+    let reference = Reference::parse(reference);
+
+    let (scheme, authority, path, query) = if reference.scheme.is_some() {
+        (
+            reference.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.authority.is_some() {
+        (
+            base.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.path.is_empty() {
+        (
+            base.scheme,
+            base.authority,
+            base.path.to_owned(),
+            reference.query.or(base.query),
+        )
+    } else if reference.path.starts_with('/') {
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else {
+        let merged = if base.authority.is_some() && base.path.is_empty() {
+            format!("/{}", reference.path)
+        } else {
+            let directory = base.path.rfind('/').map_or("", |slash| &base.path[..=slash]);
+            format!("{directory}{}", reference.path)
+        };
+
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(&merged),
+            reference.query,
+        )
+    };
+
+    let mut result = String::new();
+    if let Some(scheme) = scheme {
+        result.push_str(scheme);
+        result.push(':');
+    }
+    if let Some(authority) = authority {
+        result.push_str("//");
+        result.push_str(authority);
+    }
+    result.push_str(&path);
+    if let Some(query) = query {
+        result.push('?');
+        result.push_str(query);
+    }
+    if let Some(fragment) = reference.fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+
+    result
+}
+
+/// Remove the `.` and `..` segments from a path, as described by
+/// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4).
+fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::new();
+
+    while !input.is_empty() {
+        if let Some(rest) = input.strip_prefix("../") {
+            input = rest;
+        } else if let Some(rest) = input.strip_prefix("./") {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") {
+            input = &input[3..];
+            output.truncate(output.rfind('/').unwrap_or(0));
+        } else if input == "/.." {
+            input = "/";
+            output.truncate(output.rfind('/').unwrap_or(0));
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            let start = usize::from(input.starts_with('/'));
+            let end = input[start..].find('/').map_or(input.len(), |end| start + end);
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        check!(
+            Reference::parse("http://x.io/a/b?q=1#top")
+                == Reference {
+                    scheme:    Some("http"),
+                    authority: Some("x.io"),
+                    path:      "/a/b",
+                    query:     Some("q=1"),
+                    fragment:  Some("top"),
+                }
+        );
+        check!(
+            Reference::parse("a:b/c")
+                == Reference {
+                    scheme:    Some("a"),
+                    authority: None,
+                    path:      "b/c",
+                    query:     None,
+                    fragment:  None,
+                }
+        );
+        check!(Reference::parse("./a:b").scheme == None);
+        check!(Reference::parse("?page=2").path == "");
+    }
+
+    /// The examples from section 5.4 of RFC 3986.
+    #[test]
+    fn resolve_examples() {
+        let base = "http://a/b/c/d;p?q";
+        let examples = [
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            (";x", "http://a/b/c/;x"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../g", "http://a/g"),
+            ("../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+        ];
+
+        for (reference, expected) in examples {
+            check!(resolve(base, reference) == expected, "{reference}");
+        }
+    }
+
+    #[test]
+    fn resolve_against_paths() {
+        check!(resolve("/orders/42", "items") == "/orders/items");
+        check!(resolve("/orders/42", "../customers/./pj123") == "/customers/pj123");
+        check!(resolve("/orders/42", "?page=2") == "/orders/42?page=2");
+        check!(resolve("http://x.io", "c") == "http://x.io/c");
+    }
+}
@@ -0,0 +1,445 @@
+use std::borrow::Cow;
+
+use derive_more::Display;
+use http::StatusCode;
+#[cfg(feature = "patterns")]
+use regex_lite::Regex;
+use serde_json::Value;
+
+use crate::{Action, ErrorDocument, Field, FieldOption};
+
+/// The ways in which a submitted value can break the constraints of a field.
+#[derive(Debug, Clone, PartialEq, Display)]
+pub enum Violation {
+    /// The field is required but no value was submitted.
+    #[display(fmt = "is required")]
+    Missing,
+    /// Several values were submitted for a field that only accepts one.
+    #[display(fmt = "only accepts a single value")]
+    NotMultiple,
+    /// The field has numeric constraints, but the value is not a number.
+    #[display(fmt = "must be a number")]
+    NotANumber,
+    /// The value is less than the minimum of the field.
+    #[display(fmt = "must be at least {_0}")]
+    BelowMinimum(f64),
+    /// The value is greater than the maximum of the field.
+    #[display(fmt = "must be at most {_0}")]
+    AboveMaximum(f64),
+    /// The value is not a whole number of steps from the base of the field.
+    #[display(fmt = "must be in steps of {_0}")]
+    InvalidStep(f64),
+    /// The value is shorter than the minimum length of the field.
+    #[display(fmt = "must be at least {_0} characters long")]
+    TooShort(usize),
+    /// The value is longer than the maximum length of the field.
+    #[display(fmt = "must be at most {_0} characters long")]
+    TooLong(usize),
+    /// The value does not match the pattern of the field.
+    #[display(fmt = "must match the pattern {_0}")]
+    PatternMismatch(String),
+    /// The field has a pattern that can not be checked, either because it is not a valid regular
+    /// expression or because the `patterns` feature is not enabled.
+    #[display(fmt = "has a pattern that can not be checked: {_0}")]
+    UncheckablePattern(String),
+    /// The value is not one of the options of the field.
+    #[display(fmt = "must be one of the available options")]
+    NotAnOption,
+}
+
+/// A single field of a submission that breaks the constraints of the field.
+#[derive(Debug, Clone, PartialEq, Display)]
+#[display(fmt = "{field} {violation}")]
+pub struct FieldError {
+    pub field:     String,
+    pub violation: Violation,
+}
+
+/// Error from validating a submission against the fields of an action, listing every field that
+/// breaks its constraints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub action: String,
+    pub errors: Vec<FieldError>,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let errors = self
+            .errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "Invalid submission for {}: {errors}", self.action)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Action {
+    /// Validate a submission to this action against the constraints of its fields.
+    ///
+    /// The submission is a JSON object of field names to values. Values may be strings, as they
+    /// would be when decoded from a form submission, in which case numeric constraints are applied
+    /// to the number that they contain. Fields that have no value are only checked for being
+    /// required, and values for names that are not fields of the action are ignored.
+    ///
+    /// # Parameters
+    /// - `submission` - The submitted values.
+    ///
+    /// # Errors
+    /// If any field breaks its constraints, an error listing every one that does.
+    pub fn validate(&self, submission: &Value) -> Result<(), ValidationError> {
+        let errors: Vec<FieldError> = self
+            .fields
+            .iter()
+            .filter_map(|field| {
+                field
+                    .validate(submission.get(&field.name))
+                    .err()
+                    .map(|violation| FieldError {
+                        field: field.name.clone(),
+                        violation,
+                    })
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError {
+                action: self.name.clone(),
+                errors,
+            })
+        }
+    }
+}
+
+impl Field {
+    /// Validate a submitted value against the constraints of this field.
+    ///
+    /// # Parameters
+    /// - `value` - The submitted value, if there was one.
+    ///
+    /// # Errors
+    /// The first constraint that the value breaks.
+    pub fn validate(&self, value: Option<&Value>) -> Result<(), Violation> {
+        let values = match value {
+            None | Some(Value::Null) => vec![],
+            Some(Value::Array(values)) => values.iter().collect(),
+            Some(value) => vec![value],
+        };
+        let values: Vec<&Value> = values.into_iter().filter(|v| !is_empty(v)).collect();
+
+        if values.is_empty() {
+            return if self.required {
+                Err(Violation::Missing)
+            } else {
+                Ok(())
+            };
+        }
+
+        if values.len() > 1 && !self.multiple {
+            return Err(Violation::NotMultiple);
+        }
+
+        #[cfg(not(feature = "patterns"))]
+        if let Some(pattern) = &self.pattern {
+            return Err(Violation::UncheckablePattern(pattern.clone()));
+        }
+        #[cfg(feature = "patterns")]
+        let pattern = self.compile_pattern()?;
+
+        let options = self.options();
+        values.into_iter().try_for_each(|value| {
+            self.validate_single(value, &options)?;
+
+            #[cfg(feature = "patterns")]
+            if let (Some((pattern, regex)), Some(text)) = (&pattern, as_text(value)) {
+                if !regex.is_match(&text) {
+                    return Err(Violation::PatternMismatch((*pattern).to_owned()));
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Compile the pattern of this field, if it has one, alongside the pattern itself. As for
+    /// HTML, the pattern must match the entire value.
+    ///
+    /// # Errors
+    /// If the pattern is not a valid regular expression.
+    #[cfg(feature = "patterns")]
+    fn compile_pattern(&self) -> Result<Option<(&str, Regex)>, Violation> {
+        self.pattern
+            .as_deref()
+            .map(|pattern| match Regex::new(&format!("^(?:{pattern})$")) {
+                Ok(regex) => Ok((pattern, regex)),
+                Err(_) => Err(Violation::UncheckablePattern(pattern.to_owned())),
+            })
+            .transpose()
+    }
+
+    /// Validate a single, non-empty, submitted value against the constraints of this field.
+    fn validate_single(&self, value: &Value, options: &[FieldOption]) -> Result<(), Violation> {
+        if self.min.is_some() || self.max.is_some() || self.step.is_some() {
+            let number = match value {
+                Value::Number(number) => number.as_f64(),
+                Value::String(text) => text.trim().parse::<f64>().ok(),
+                _ => None,
+            }
+            .ok_or(Violation::NotANumber)?;
+
+            if let Some(min) = self.min.filter(|min| number < *min) {
+                return Err(Violation::BelowMinimum(min));
+            }
+            if let Some(max) = self.max.filter(|max| number > *max) {
+                return Err(Violation::AboveMaximum(max));
+            }
+            if let Some(step) = self.step.filter(|step| *step > 0.0) {
+                let steps = (number - self.min.unwrap_or(0.0)) / step;
+                if (steps - steps.round()).abs() > 1e-9 {
+                    return Err(Violation::InvalidStep(step));
+                }
+            }
+        }
+
+        if let Some(text) = as_text(value) {
+            let length = text.chars().count();
+
+            if let Some(min_length) = self.min_length.filter(|min| length < *min) {
+                return Err(Violation::TooShort(min_length));
+            }
+            if let Some(max_length) = self.max_length.filter(|max| length > *max) {
+                return Err(Violation::TooLong(max_length));
+            }
+        }
+
+        if !options.is_empty() && !options.iter().any(|option| same_value(&option.value, value)) {
+            return Err(Violation::NotAnOption);
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ValidationError> for ErrorDocument {
+    /// Convert the validation error into a `422 Unprocessable Entity` error document, with a
+    /// detail listing every field that breaks its constraints.
+    fn from(error: ValidationError) -> Self {
+        let detail = error
+            .errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        ErrorDocument::new(StatusCode::UNPROCESSABLE_ENTITY).with_detail(detail)
+    }
+}
+
+/// Check if a submitted value counts as not having been given.
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        _ => false,
+    }
+}
+
+/// Get the textual form of a scalar value, as it would appear in a form submission.
+fn as_text(value: &Value) -> Option<Cow<'_, str>> {
+    match value {
+        Value::String(text) => Some(Cow::Borrowed(text)),
+        Value::Number(number) => Some(Cow::Owned(number.to_string())),
+        Value::Bool(flag) => Some(Cow::Owned(flag.to_string())),
+        _ => None,
+    }
+}
+
+/// Check if two values are the same, treating values with the same textual form as equal so that
+/// form submissions of numbers and booleans match options that are not strings.
+fn same_value(a: &Value, b: &Value) -> bool {
+    a == b || matches!((as_text(a), as_text(b)), (Some(a), Some(b)) if a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+    use serde_json::json;
+
+    use super::*;
+    use crate::values::FieldTypes;
+
+    fn action() -> Action {
+        Action::new("add-item", "/orders/42/items")
+            .with_field(
+                Field::new("productCode")
+                    .with_required()
+                    .with_min_length(3)
+                    .with_max_length(8),
+            )
+            .with_field(
+                Field::new("quantity")
+                    .with_min(1.0)
+                    .with_max(99.0)
+                    .with_step(1.0),
+            )
+            .with_field(
+                Field::new("status")
+                    .with_option(FieldOption::new("pending").with_title("Pending"))
+                    .with_option(FieldOption::new("paid").with_title("Paid")),
+            )
+    }
+
+    #[test]
+    fn serialize_constraints() {
+        let action = action();
+
+        check!(
+            serde_json::to_value(&action.fields).unwrap()
+                == json!([
+                    {
+                        "name": "productCode",
+                        "required": true,
+                        "minLength": 3,
+                        "maxLength": 8
+                    },
+                    {"name": "quantity", "min": 1.0, "max": 99.0, "step": 1.0},
+                    {
+                        "name": "status",
+                        "value": [
+                            {"value": "pending", "title": "Pending"},
+                            {"value": "paid", "title": "Paid"}
+                        ]
+                    }
+                ])
+        );
+
+        let parsed: Vec<Field> =
+            serde_json::from_value(serde_json::to_value(&action.fields).unwrap()).unwrap();
+        check!(parsed[0].required);
+        check!(parsed[1].max == Some(99.0));
+        check!(parsed[2].options().len() == 2);
+
+        let field = Field::new("productCode").with_pattern("[A-Z]+-[0-9]+");
+        check!(
+            serde_json::to_value(&field).unwrap()
+                == json!({"name": "productCode", "pattern": "[A-Z]+-[0-9]+"})
+        );
+    }
+
+    #[test]
+    fn valid_submission() {
+        check!(action()
+            .validate(&json!({"productCode": "AB-12", "quantity": "5", "status": "paid"}))
+            .is_ok());
+        check!(action().validate(&json!({"productCode": "AB-12"})).is_ok());
+    }
+
+    #[test]
+    fn invalid_submission() {
+        let_assert!(
+            Err(error) = action().validate(&json!({"quantity": 100, "status": "shipped"}))
+        );
+
+        check!(error.action == "add-item");
+        check!(
+            error.errors
+                == vec![
+                    FieldError {
+                        field:     "productCode".to_owned(),
+                        violation: Violation::Missing,
+                    },
+                    FieldError {
+                        field:     "quantity".to_owned(),
+                        violation: Violation::AboveMaximum(99.0),
+                    },
+                    FieldError {
+                        field:     "status".to_owned(),
+                        violation: Violation::NotAnOption,
+                    },
+                ]
+        );
+
+        let document = ErrorDocument::from(error);
+        check!(document.status_code() == StatusCode::UNPROCESSABLE_ENTITY);
+        check!(
+            document.document.properties.detail.as_deref()
+                == Some(
+                    "productCode is required, quantity must be at most 99, status must be one of \
+                     the available options"
+                )
+        );
+    }
+
+    #[test]
+    fn field_violations() {
+        let product = &action().fields[0];
+        check!(product.validate(Some(&json!("AB"))) == Err(Violation::TooShort(3)));
+        check!(product.validate(Some(&json!("ABCD-1234"))) == Err(Violation::TooLong(8)));
+        check!(product.validate(Some(&json!(["AB-1", "AB-2"]))) == Err(Violation::NotMultiple));
+
+        let quantity = &action().fields[1];
+        check!(quantity.validate(Some(&json!("lots"))) == Err(Violation::NotANumber));
+        check!(quantity.validate(Some(&json!(0))) == Err(Violation::BelowMinimum(1.0)));
+        check!(quantity.validate(Some(&json!(2.5))) == Err(Violation::InvalidStep(1.0)));
+        check!(quantity.validate(Some(&json!(""))) == Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "patterns")]
+    fn patterns() {
+        let product = Field::new("productCode").with_pattern("[A-Z]+-[0-9]+");
+        check!(
+            product.validate(Some(&json!("ab-12")))
+                == Err(Violation::PatternMismatch("[A-Z]+-[0-9]+".to_owned()))
+        );
+        check!(product.validate(Some(&json!(["AB-12"]))) == Ok(()));
+
+        let invalid = Field::new("code").with_pattern("[A-Z");
+        check!(
+            invalid.validate(Some(&json!("anything")))
+                == Err(Violation::UncheckablePattern("[A-Z".to_owned()))
+        );
+        check!(invalid.validate(None) == Ok(()));
+    }
+
+    #[test]
+    #[cfg(not(feature = "patterns"))]
+    fn unchecked_patterns() {
+        let product = Field::new("productCode").with_pattern("[A-Z]+-[0-9]+");
+        check!(
+            product.validate(Some(&json!("AB-12")))
+                == Err(Violation::UncheckablePattern("[A-Z]+-[0-9]+".to_owned()))
+        );
+        check!(Field::new("code").validate(Some(&json!("anything"))) == Ok(()));
+    }
+
+    #[test]
+    fn radio_group() {
+        let field = Field::new("shipping")
+            .with_type(FieldTypes::Radio)
+            .with_required()
+            .with_option(FieldOption::new("standard").with_selected())
+            .with_option(FieldOption::new("express"));
+
+        check!(field.validate(Some(&json!("express"))) == Ok(()));
+        check!(field.validate(Some(&json!("overnight"))) == Err(Violation::NotAnOption));
+        check!(field.validate(None) == Err(Violation::Missing));
+    }
+
+    #[test]
+    fn multiple_values() {
+        let field = Field::new("tags")
+            .with_multiple()
+            .with_option(FieldOption::new(1))
+            .with_option(FieldOption::new(2));
+
+        check!(field.validate(Some(&json!(["1", 2]))) == Ok(()));
+        check!(field.validate(Some(&json!([1, 3]))) == Err(Violation::NotAnOption));
+    }
+}
@@ -1,4 +1,5 @@
 use derive_more::Display;
+use http::Method;
 
 /// Enumeration of values for field types.
 #[derive(Debug, Display)]
@@ -54,6 +55,18 @@ pub enum HttpMethods {
     DELETE,
 }
 
+impl From<HttpMethods> for Method {
+    fn from(value: HttpMethods) -> Self {
+        match value {
+            HttpMethods::GET => Method::GET,
+            HttpMethods::POST => Method::POST,
+            HttpMethods::PUT => Method::PUT,
+            HttpMethods::PATCH => Method::PATCH,
+            HttpMethods::DELETE => Method::DELETE,
+        }
+    }
+}
+
 /// Enumeration of values for Link Relations.
 /// Derived from the IANA list at <https://www.iana.org/assignments/link-relations/link-relations.xhtml>.
 #[derive(Debug, Display)]
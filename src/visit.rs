@@ -0,0 +1,419 @@
+use serde::Serialize;
+
+use crate::{Action, Document, EmbeddedRepresentation, Entity, Field, Link};
+
+/// Visitor over the tree of a Siren document, for inspecting every entity, link, action and field
+/// within it without writing the traversal each time.
+///
+/// Every method defaults to visiting the children of the element, using the matching `walk_*`
+/// function, so implementations only override the methods for the elements they care about. An
+/// overriding method must call the `walk_*` function itself to carry on into the children.
+///
+/// ```rust
+/// # use http_siren::{visit::Visitor, Document, Link};
+/// # use serde_json::json;
+/// #[derive(Default)]
+/// struct Hrefs(Vec<String>);
+///
+/// impl Visitor for Hrefs {
+///     fn visit_link(&mut self, link: &Link) {
+///         self.0.push(link.href.clone());
+///     }
+/// }
+///
+/// let document = Document::new(json!({})).with_link(Link::new("/orders/42").with_rel("self"));
+///
+/// let mut hrefs = Hrefs::default();
+/// document.accept(&mut hrefs);
+/// assert_eq!(hrefs.0, vec!["/orders/42"]);
+/// ```
+pub trait Visitor {
+    /// Visit the document itself.
+    fn visit_document<T>(&mut self, document: &Document<T>)
+    where
+        T: Serialize,
+    {
+        walk_document(self, document);
+    }
+
+    /// Visit a sub-entity, either an embedded link or an embedded representation.
+    fn visit_entity(&mut self, entity: &Entity) {
+        walk_entity(self, entity);
+    }
+
+    /// Visit an embedded representation.
+    fn visit_embedded_representation(&mut self, representation: &EmbeddedRepresentation) {
+        walk_embedded_representation(self, representation);
+    }
+
+    /// Visit a link, which is either one of the links of an entity or an embedded link.
+    fn visit_link(&mut self, _link: &Link) {}
+
+    /// Visit an action.
+    fn visit_action(&mut self, action: &Action) {
+        walk_action(self, action);
+    }
+
+    /// Visit a field of an action.
+    fn visit_field(&mut self, _field: &Field) {}
+}
+
+/// Visit the entities, links and actions of a document.
+pub fn walk_document<V, T>(visitor: &mut V, document: &Document<T>)
+where
+    V: Visitor + ?Sized,
+    T: Serialize,
+{
+    walk_contents(visitor, &document.entities, &document.links, &document.actions);
+}
+
+/// Visit the link or representation of a sub-entity.
+pub fn walk_entity<V>(visitor: &mut V, entity: &Entity)
+where
+    V: Visitor + ?Sized,
+{
+    match entity {
+        Entity::Link(link) => visitor.visit_link(link),
+        Entity::Representation(representation) => {
+            visitor.visit_embedded_representation(representation);
+        },
+    }
+}
+
+/// Visit the entities, links and actions of an embedded representation.
+pub fn walk_embedded_representation<V>(visitor: &mut V, representation: &EmbeddedRepresentation)
+where
+    V: Visitor + ?Sized,
+{
+    walk_contents(
+        visitor,
+        &representation.entities,
+        &representation.links,
+        &representation.actions,
+    );
+}
+
+/// Visit the fields of an action.
+pub fn walk_action<V>(visitor: &mut V, action: &Action)
+where
+    V: Visitor + ?Sized,
+{
+    for field in &action.fields {
+        visitor.visit_field(field);
+    }
+}
+
+fn walk_contents<V>(visitor: &mut V, entities: &[Entity], links: &[Link], actions: &[Action])
+where
+    V: Visitor + ?Sized,
+{
+    for entity in entities {
+        visitor.visit_entity(entity);
+    }
+    for link in links {
+        visitor.visit_link(link);
+    }
+    for action in actions {
+        visitor.visit_action(action);
+    }
+}
+
+/// Visitor that can modify the tree of a Siren document, for implementing cross-cutting concerns
+/// such as rewriting hrefs, filtering out actions or localising titles once for every document.
+///
+/// As with [`Visitor`], every method defaults to visiting the children of the element using the
+/// matching `walk_*_mut` function. The methods for sub-entities, links, actions and fields also
+/// decide whether the element is kept, so returning `false` removes it from the document.
+///
+/// ```rust
+/// # use http_siren::{visit::VisitorMut, Action, Document};
+/// # use serde_json::json;
+/// struct Deprecate;
+///
+/// impl VisitorMut for Deprecate {
+///     fn visit_action_mut(&mut self, action: &mut Action) -> bool {
+///         if action.name == "cancel" {
+///             action.class.push("deprecated".to_owned());
+///         }
+///         true
+///     }
+/// }
+///
+/// let mut document = Document::new(json!({})).with_action(Action::new("cancel", "/orders/42"));
+/// document.accept_mut(&mut Deprecate);
+/// assert_eq!(document.actions[0].class, vec!["deprecated"]);
+/// ```
+pub trait VisitorMut {
+    /// Visit the document itself.
+    fn visit_document_mut<T>(&mut self, document: &mut Document<T>)
+    where
+        T: Serialize,
+    {
+        walk_document_mut(self, document);
+    }
+
+    /// Visit a sub-entity, either an embedded link or an embedded representation. Returns whether
+    /// to keep the sub-entity.
+    fn visit_entity_mut(&mut self, entity: &mut Entity) -> bool {
+        walk_entity_mut(self, entity)
+    }
+
+    /// Visit an embedded representation. Returns whether to keep the representation.
+    fn visit_embedded_representation_mut(
+        &mut self,
+        representation: &mut EmbeddedRepresentation,
+    ) -> bool {
+        walk_embedded_representation_mut(self, representation);
+
+        true
+    }
+
+    /// Visit a link, which is either one of the links of an entity or an embedded link. Returns
+    /// whether to keep the link.
+    fn visit_link_mut(&mut self, _link: &mut Link) -> bool {
+        true
+    }
+
+    /// Visit an action. Returns whether to keep the action.
+    fn visit_action_mut(&mut self, action: &mut Action) -> bool {
+        walk_action_mut(self, action);
+
+        true
+    }
+
+    /// Visit a field of an action. Returns whether to keep the field.
+    fn visit_field_mut(&mut self, _field: &mut Field) -> bool {
+        true
+    }
+}
+
+/// Visit the entities, links and actions of a document, removing those that the visitor rejects.
+pub fn walk_document_mut<V, T>(visitor: &mut V, document: &mut Document<T>)
+where
+    V: VisitorMut + ?Sized,
+    T: Serialize,
+{
+    walk_contents_mut(
+        visitor,
+        &mut document.entities,
+        &mut document.links,
+        &mut document.actions,
+    );
+}
+
+/// Visit the link or representation of a sub-entity, returning whether the visitor keeps it.
+pub fn walk_entity_mut<V>(visitor: &mut V, entity: &mut Entity) -> bool
+where
+    V: VisitorMut + ?Sized,
+{
+    match entity {
+        Entity::Link(link) => visitor.visit_link_mut(link),
+        Entity::Representation(representation) => {
+            visitor.visit_embedded_representation_mut(representation)
+        },
+    }
+}
+
+/// Visit the entities, links and actions of an embedded representation, removing those that the
+/// visitor rejects.
+pub fn walk_embedded_representation_mut<V>(
+    visitor: &mut V,
+    representation: &mut EmbeddedRepresentation,
+) where
+    V: VisitorMut + ?Sized,
+{
+    walk_contents_mut(
+        visitor,
+        &mut representation.entities,
+        &mut representation.links,
+        &mut representation.actions,
+    );
+}
+
+/// Visit the fields of an action, removing those that the visitor rejects.
+pub fn walk_action_mut<V>(visitor: &mut V, action: &mut Action)
+where
+    V: VisitorMut + ?Sized,
+{
+    retain_mut(&mut action.fields, |field| visitor.visit_field_mut(field));
+}
+
+fn walk_contents_mut<V>(
+    visitor: &mut V,
+    entities: &mut Vec<Entity>,
+    links: &mut Vec<Link>,
+    actions: &mut Vec<Action>,
+) where
+    V: VisitorMut + ?Sized,
+{
+    retain_mut(entities, |entity| visitor.visit_entity_mut(entity));
+    retain_mut(links, |link| visitor.visit_link_mut(link));
+    retain_mut(actions, |action| visitor.visit_action_mut(action));
+}
+
+/// Keep only the items for which the callback returns `true`, allowing it to modify them, as
+/// `Vec::retain_mut` does in newer versions of Rust.
+fn retain_mut<T, F>(items: &mut Vec<T>, mut keep: F)
+where
+    F: FnMut(&mut T) -> bool,
+{
+    let mut kept = Vec::with_capacity(items.len());
+    for mut item in items.drain(..) {
+        if keep(&mut item) {
+            kept.push(item);
+        }
+    }
+
+    *items = kept;
+}
+
+impl<T> Document<T>
+where
+    T: Serialize,
+{
+    /// Visit every element of this document.
+    ///
+    /// # Parameters
+    /// - `visitor` - The visitor.
+    pub fn accept<V>(&self, visitor: &mut V)
+    where
+        V: Visitor,
+    {
+        visitor.visit_document(self);
+    }
+
+    /// Visit every element of this document, allowing the visitor to modify or remove them.
+    ///
+    /// # Parameters
+    /// - `visitor` - The visitor.
+    pub fn accept_mut<V>(&mut self, visitor: &mut V)
+    where
+        V: VisitorMut,
+    {
+        visitor.visit_document_mut(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn document() -> Document<Value> {
+        Document::new(json!({"orderNumber": 42}))
+            .with_link(Link::new("/orders/42").with_rel("self"))
+            .with_embedded_link(Link::new("/orders/42/items").with_rel("items"))
+            .with_embedded_representation(
+                EmbeddedRepresentation::new(json!({"customerId": "pj123"}))
+                    .with_rel("customer")
+                    .with_link(Link::new("/customers/pj123").with_rel("self"))
+                    .with_action(Action::new("rename", "/customers/pj123")),
+            )
+            .with_action(
+                Action::new("add-item", "/orders/42/items")
+                    .with_field(Field::new("productCode"))
+                    .with_field(Field::new("internalCode")),
+            )
+    }
+
+    /// Records the order in which elements are visited.
+    #[derive(Default)]
+    struct Trace(Vec<String>);
+
+    impl Visitor for Trace {
+        fn visit_embedded_representation(&mut self, representation: &EmbeddedRepresentation) {
+            self.0.push(format!("representation {}", representation.rel.join(",")));
+            walk_embedded_representation(self, representation);
+        }
+
+        fn visit_link(&mut self, link: &Link) {
+            self.0.push(format!("link {}", link.href));
+        }
+
+        fn visit_action(&mut self, action: &Action) {
+            self.0.push(format!("action {}", action.name));
+            walk_action(self, action);
+        }
+
+        fn visit_field(&mut self, field: &Field) {
+            self.0.push(format!("field {}", field.name));
+        }
+    }
+
+    #[test]
+    fn visit_in_order() {
+        let mut trace = Trace::default();
+        document().accept(&mut trace);
+
+        check!(
+            trace.0
+                == vec![
+                    "link /orders/42/items",
+                    "representation customer",
+                    "link /customers/pj123",
+                    "action rename",
+                    "link /orders/42",
+                    "action add-item",
+                    "field productCode",
+                    "field internalCode",
+                ]
+        );
+    }
+
+    /// Rewrites hrefs onto another origin, and removes anything internal.
+    struct Rewrite;
+
+    impl VisitorMut for Rewrite {
+        fn visit_link_mut(&mut self, link: &mut Link) -> bool {
+            link.href = format!("https://api.x.io{}", link.href);
+            true
+        }
+
+        fn visit_action_mut(&mut self, action: &mut Action) -> bool {
+            action.href = format!("https://api.x.io{}", action.href);
+            walk_action_mut(self, action);
+            action.name != "rename"
+        }
+
+        fn visit_field_mut(&mut self, field: &mut Field) -> bool {
+            !field.name.starts_with("internal")
+        }
+    }
+
+    #[test]
+    fn modify_and_remove() {
+        let mut document = document();
+        document.accept_mut(&mut Rewrite);
+
+        check!(document.links[0].href == "https://api.x.io/orders/42");
+        check!(document.actions[0].href == "https://api.x.io/orders/42/items");
+        check!(document.actions[0].fields.len() == 1);
+        check!(document.actions[0].fields[0].name == "productCode");
+
+        let customer = match &document.entities[1] {
+            Entity::Representation(customer) => customer,
+            Entity::Link(_) => unreachable!(),
+        };
+        check!(customer.links[0].href == "https://api.x.io/customers/pj123");
+        check!(customer.actions.is_empty());
+    }
+
+    /// Removes every embedded representation.
+    struct LinksOnly;
+
+    impl VisitorMut for LinksOnly {
+        fn visit_entity_mut(&mut self, entity: &mut Entity) -> bool {
+            matches!(entity, Entity::Link(_))
+        }
+    }
+
+    #[test]
+    fn remove_entities() {
+        let mut document = document();
+        document.accept_mut(&mut LinksOnly);
+
+        check!(document.entities.len() == 1);
+    }
+}